@@ -0,0 +1,217 @@
+use std::{collections::HashMap, time::Duration};
+
+use ethers::{
+    providers::{Middleware, Provider},
+    types::Address,
+};
+use holaplex_hub_nfts_polygon_core::{db::Connection, Contract, ProcessorError};
+use holaplex_hub_nfts_polygon_entity::collections;
+use hub_core::{clap, prelude::*, serde_json};
+use serde::Deserialize;
+
+use crate::{
+    contracts::{Binding, EditionContracts},
+    permits::PermitArgs,
+};
+
+#[derive(Debug, clap::Args)]
+pub struct ChainArgs {
+    #[arg(long, env, default_value = "polygon")]
+    pub default_network: String,
+
+    #[arg(long, env)]
+    pub chains: Option<String>,
+
+    #[arg(long, env, default_value_t = 3600)]
+    pub contract_owner_ttl_secs: u64,
+
+    #[arg(long, env)]
+    pub project_contracts: Option<String>,
+}
+
+impl ChainArgs {
+    /// The additional networks given as a JSON list of [`ChainConfig`]s
+    ///
+    /// # Errors
+    /// This function fails if the list is not valid JSON
+    pub fn extra_chains(&self) -> Result<Vec<ChainConfig>> {
+        self.chains.as_deref().map_or(Ok(Vec::new()), |chains| {
+            serde_json::from_str(chains).context("failed to parse chains")
+        })
+    }
+
+    /// The project contracts to register given as a JSON list of [`ProjectContract`]s
+    ///
+    /// # Errors
+    /// This function fails if the list is not valid JSON
+    pub fn project_contracts(&self) -> Result<Vec<ProjectContract>> {
+        self.project_contracts
+            .as_deref()
+            .map_or(Ok(Vec::new()), |contracts| {
+                serde_json::from_str(contracts).context("failed to parse project contracts")
+            })
+    }
+}
+
+/// An edition contract deployed for a single project, which its new drops are created on
+/// instead of the default contract of the network
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProjectContract {
+    pub network: String,
+    pub project_id: String,
+    pub address: Address,
+}
+
+/// Connection details of one EVM network
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChainConfig {
+    pub network: String,
+    #[serde(default)]
+    pub chain_id: Option<u64>,
+    pub rpc_url: String,
+    pub edition_contract: Address,
+    #[serde(default)]
+    pub deployer: Option<Address>,
+}
+
+/// Edition contracts of every configured network keyed by network name
+#[derive(Clone)]
+pub struct Chains {
+    default: String,
+    chains: Arc<HashMap<String, EditionContracts>>,
+}
+
+impl Chains {
+    /// Connects to every configured network, checking that each RPC endpoint serves the
+    /// chain it is configured for, loading the owner of its default contract and checking
+    /// that the contract hashes permits like the configured EIP-712 domain
+    ///
+    /// # Errors
+    /// This function fails if a network cannot be reached, serves another chain, is
+    /// configured twice or if the default network is not configured
+    pub async fn connect(
+        default: String,
+        configs: Vec<ChainConfig>,
+        permits: &PermitArgs,
+        owner_ttl: Duration,
+    ) -> Result<Self> {
+        let mut chains = HashMap::new();
+
+        for ChainConfig {
+            network,
+            chain_id,
+            rpc_url,
+            edition_contract,
+            deployer,
+        } in configs
+        {
+            let provider = Arc::new(Provider::try_from(rpc_url)?);
+            let served = provider
+                .get_chainid()
+                .await
+                .with_context(|| format!("failed to get chain id of {network}"))?
+                .as_u64();
+
+            if let Some(chain_id) = chain_id {
+                if chain_id != served {
+                    bail!("{network} is configured for chain {chain_id} but its rpc serves chain {served}");
+                }
+            }
+
+            let contracts = EditionContracts::new(
+                network.clone(),
+                provider,
+                served,
+                permits,
+                edition_contract,
+                deployer,
+                owner_ttl,
+            );
+
+            contracts
+                .warm()
+                .await
+                .with_context(|| format!("failed to warm edition contracts of {network}"))?;
+
+            if chains.insert(network.clone(), contracts).is_some() {
+                bail!("network {network} is configured twice");
+            }
+        }
+
+        if !chains.contains_key(&default) {
+            bail!("default network {default} is not configured");
+        }
+
+        Ok(Self {
+            default,
+            chains: Arc::new(chains),
+        })
+    }
+
+    /// Registers the contracts of projects with their own edition contract. Registering is
+    /// idempotent so every replica applies the same configuration at startup.
+    ///
+    /// # Errors
+    /// This function fails if a contract is given for a network that is not configured, a
+    /// project id is invalid or the registry cannot be written
+    pub async fn register_contracts(
+        &self,
+        db: &Connection,
+        contracts: Vec<ProjectContract>,
+    ) -> Result<()> {
+        for ProjectContract {
+            network,
+            project_id,
+            address,
+        } in contracts
+        {
+            self.get(&network)?;
+
+            let project_id = project_id
+                .parse()
+                .with_context(|| format!("invalid project id {project_id}"))?;
+
+            Contract::register(db, &network, &format!("{address:?}"), project_id)
+                .await
+                .with_context(|| format!("failed to register contract {address:?} of {network}"))?;
+        }
+
+        Ok(())
+    }
+
+    /// The network a drop requested, falling back to the default network
+    #[must_use]
+    pub fn network<'a>(&'a self, requested: &'a str) -> &'a str {
+        if requested.is_empty() {
+            &self.default
+        } else {
+            requested
+        }
+    }
+
+    /// The edition contracts of every configured network
+    #[must_use]
+    pub fn networks(&self) -> impl Iterator<Item = &EditionContracts> {
+        self.chains.values()
+    }
+
+    /// # Errors
+    /// This function fails if `network` is not configured
+    pub fn get(&self, network: &str) -> Result<&EditionContracts, ProcessorError> {
+        self.chains
+            .get(network)
+            .ok_or_else(|| ProcessorError::UnknownNetwork(network.to_owned()))
+    }
+
+    /// The contract a drop was created on, on the network it was created on
+    ///
+    /// # Errors
+    /// This function fails if the drop's network is not configured or its stored contract
+    /// address is invalid
+    pub fn for_collection(
+        &self,
+        collection: &collections::Model,
+    ) -> Result<Binding, ProcessorError> {
+        self.get(&collection.network)?.for_collection(collection)
+    }
+}
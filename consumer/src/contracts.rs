@@ -0,0 +1,280 @@
+use std::{
+    collections::HashMap,
+    sync::{PoisonError, RwLock},
+    time::{Duration, Instant},
+};
+
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::{Address, H256, U256},
+};
+use holaplex_hub_nfts_polygon_core::{
+    db::Connection,
+    edition_contract,
+    eip712::{Permit, PermitDomain},
+    metrics::time_rpc,
+    Contract, ProcessorError,
+};
+use holaplex_hub_nfts_polygon_entity::collections;
+use hub_core::{prelude::*, uuid::Uuid};
+
+use crate::{permits::PermitArgs, EditionContract};
+
+/// An edition contract binding together with the EIP-712 domain its permits are signed
+/// against
+#[derive(Clone)]
+pub struct Binding {
+    pub contract: EditionContract,
+    pub permit_domain: PermitDomain,
+    pub chain_id: u64,
+}
+
+impl Binding {
+    /// Checks that the locally computed typed data hash of `permit` matches the contract's
+    /// `getHashTypedDataV4`
+    ///
+    /// # Errors
+    /// This function fails if the contract cannot be called or the hashes differ
+    pub async fn verify_permit_hash(&self, permit: &Permit) -> Result<(), ProcessorError> {
+        let hash = self.permit_domain.hash(permit);
+        let contract_hash = time_rpc(
+            "getHashTypedDataV4",
+            self.contract
+                .get_hash_typed_data_v4(
+                    permit.owner,
+                    permit.spender,
+                    permit.token_id,
+                    permit.value,
+                    permit.deadline,
+                )
+                .call(),
+        )
+        .await?;
+
+        if contract_hash != hash.to_fixed_bytes() {
+            return Err(anyhow!(
+                "local permit hash {hash:?} does not match contract hash {:?}",
+                H256(contract_hash)
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Edition contract bindings of one network keyed by contract address.
+///
+/// Drops stay on the contract they were created on. New drops use the contract registered
+/// for their project, or the configured default contract when the project has none.
+#[derive(Clone)]
+pub struct EditionContracts {
+    network: String,
+    provider: Arc<Provider<Http>>,
+    chain_id: u64,
+    deployer: Option<Address>,
+    name: String,
+    version: String,
+    default: Address,
+    bindings: Arc<RwLock<HashMap<Address, Binding>>>,
+    owner_ttl: Duration,
+    owners: Arc<RwLock<HashMap<Address, (Address, Instant)>>>,
+}
+
+impl EditionContracts {
+    #[must_use]
+    pub fn new(
+        network: String,
+        provider: Arc<Provider<Http>>,
+        chain_id: u64,
+        permits: &PermitArgs,
+        default: Address,
+        deployer: Option<Address>,
+        owner_ttl: Duration,
+    ) -> Self {
+        Self {
+            network,
+            provider,
+            chain_id,
+            deployer,
+            name: permits.polygon_edition_contract_name.clone(),
+            version: permits.polygon_edition_contract_version.clone(),
+            default,
+            bindings: Arc::default(),
+            owner_ttl,
+            owners: Arc::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn network(&self) -> &str {
+        &self.network
+    }
+
+    /// The owner of the edition contract at `address`. Uses the configured deployer when
+    /// there is one, otherwise the on-chain owner is fetched and cached for the owner TTL.
+    ///
+    /// # Errors
+    /// This function fails if the owner has to be fetched and the call fails
+    pub async fn owner(&self, address: Address) -> Result<Address, ProcessorError> {
+        if let Some(deployer) = self.deployer {
+            return Ok(deployer);
+        }
+
+        if let Some((owner, fetched_at)) = self
+            .owners
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&address)
+        {
+            if fetched_at.elapsed() < self.owner_ttl {
+                return Ok(*owner);
+            }
+        }
+
+        let owner = time_rpc("owner", self.get(address).contract.owner().call()).await?;
+
+        self.owners
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(address, (owner, Instant::now()));
+
+        Ok(owner)
+    }
+
+    /// Drops the cached owner of the edition contract at `address` so the next lookup
+    /// fetches it again
+    pub fn invalidate_owner(&self, address: Address) {
+        self.owners
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&address);
+    }
+
+    /// Checks that the RPC endpoint answers and still serves the chain it was connected to
+    ///
+    /// # Errors
+    /// This function fails if the chain id cannot be fetched or has changed
+    pub async fn check_chain_id(&self) -> Result<(), ProcessorError> {
+        let served = time_rpc("eth_chainId", self.provider.get_chainid())
+            .await?
+            .as_u64();
+
+        if served != self.chain_id {
+            return Err(anyhow!(
+                "rpc serves chain {served} instead of chain {}",
+                self.chain_id
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Fetches the owner of the default contract ahead of the first drop and checks that
+    /// every binding hashes permits like its contract, so a wrong EIP-712 domain name or
+    /// version fails startup instead of producing permits that revert on-chain
+    ///
+    /// # Errors
+    /// This function fails if the owner cannot be fetched or a permit hash differs
+    pub async fn warm(&self) -> Result<(), ProcessorError> {
+        self.owner(self.default).await?;
+        self.get(self.default);
+
+        let bindings: Vec<_> = self
+            .bindings
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .cloned()
+            .collect();
+
+        let permit = Permit {
+            owner: Address::zero(),
+            spender: Address::zero(),
+            token_id: U256::zero(),
+            value: U256::zero(),
+            deadline: U256::zero(),
+        };
+
+        for binding in bindings {
+            binding.verify_permit_hash(&permit).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the binding for `address`, creating it on first use
+    #[must_use]
+    pub fn get(&self, address: Address) -> Binding {
+        if let Some(binding) = self
+            .bindings
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&address)
+        {
+            return binding.clone();
+        }
+
+        let binding = Binding {
+            contract: Arc::new(edition_contract::EditionContract::new(
+                address,
+                self.provider.clone(),
+            )),
+            permit_domain: PermitDomain::new(
+                &self.name,
+                &self.version,
+                U256::from(self.chain_id),
+                address,
+            ),
+            chain_id: self.chain_id,
+        };
+
+        self.bindings
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(address)
+            .or_insert(binding)
+            .clone()
+    }
+
+    /// The contract new drops of `project_id` are created on
+    ///
+    /// # Errors
+    /// This function fails if the registry cannot be read or holds an invalid address
+    pub async fn for_project(
+        &self,
+        db: &Connection,
+        project_id: &str,
+    ) -> Result<Binding, ProcessorError> {
+        let project_id: Uuid = project_id
+            .parse()
+            .map_err(ProcessorError::invalid("project_id"))?;
+
+        let address = match Contract::find_by_project_id(db, &self.network, project_id).await? {
+            Some(contract) => contract
+                .address
+                .parse()
+                .map_err(ProcessorError::invalid("contract address"))?,
+            None => self.default,
+        };
+
+        Ok(self.get(address))
+    }
+
+    /// The contract a drop was created on
+    ///
+    /// # Errors
+    /// This function fails if the stored contract address is invalid
+    pub fn for_collection(
+        &self,
+        collection: &collections::Model,
+    ) -> Result<Binding, ProcessorError> {
+        let address = collection
+            .contract_address
+            .parse()
+            .map_err(ProcessorError::invalid("contract address"))?;
+
+        Ok(self.get(address))
+    }
+}
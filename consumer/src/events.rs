@@ -1,150 +1,359 @@
-use ethers::types::{Bytes, U256};
+use std::collections::{BTreeMap, HashMap};
+
+use ethers::types::{Address, Bytes, U256};
 use holaplex_hub_nfts_polygon_core::{
     db::Connection,
+    eip712::{normalize_v, recover_signer, Permit},
     proto::{
         self,
         nft_events::Event as NftEvents,
         polygon_nft_events,
         treasury_events::{EcdsaSignature, Event as TreasuryEvents, PolygonPermitHashSignature},
-        CreateEditionTransaction, MintEditionTransaction, NftEventKey, PermitArgsHash,
-        PolygonNftEventKey, PolygonNftEvents, PolygonTokenTransferTxns, PolygonTransaction,
-        TransferPolygonAsset, TreasuryEventKey, UpdateEdtionTransaction,
+        BurnPolygonAsset, CreateEditionTransaction, MintEditionBatchTransaction,
+        MintEditionTransaction, NftEventKey, PermitArgsHash, PolygonBatchMintTransaction,
+        PolygonBatchMintTransactions, PolygonNftEventKey, PolygonNftEvents,
+        PolygonTokenTransferTxns, PolygonTransaction, PolygonTransactionFailure,
+        TransferPolygonAsset, TreasuryEventKey, UpdateEdtionTransaction, UpdatePolygonRoyalty,
     },
-    sea_orm::Set,
-    Collection, EditionInfo, Mint, Services,
+    sea_orm::{DatabaseTransaction, Set, TransactionTrait},
+    validation::Validator,
+    Collection, EditionInfo, Mint, Outbox, PendingTransfer, ProcessedEvent, ProcessorError,
+    Services,
+};
+use holaplex_hub_nfts_polygon_entity::{collections, mints, pending_transfers, processed_events};
+use hub_core::{
+    chrono::Utc,
+    prelude::*,
+    tokio,
+    tracing::{field, info_span, Instrument, Span},
+    uuid::Uuid,
 };
-use holaplex_hub_nfts_polygon_entity::{collections, mints};
-use hub_core::{chrono::Utc, prelude::*, producer::Producer, uuid::Uuid};
 
-use crate::EditionContract;
+use crate::{
+    chains::Chains, contracts::Binding, fees::FeeArgs, outbox::Relay, permits::PermitArgs,
+    retry::RetryArgs, simulation::SimulationArgs,
+};
+
+/// A message the processor gave up on
+#[derive(Debug)]
+pub struct Failure {
+    pub attempts: u32,
+    pub error: ProcessorError,
+}
 
 #[derive(Clone)]
 pub struct Processor {
     db: Connection,
-    producer: Producer<PolygonNftEvents>,
-    edition_contract: EditionContract,
+    relay: Relay,
+    chains: Chains,
+    permits: PermitArgs,
+    retry: RetryArgs,
+    simulation: SimulationArgs,
+    fees: FeeArgs,
 }
 
 impl Processor {
     #[must_use]
     pub fn new(
         db: Connection,
-        producer: Producer<PolygonNftEvents>,
-        edition_contract: EditionContract,
+        relay: Relay,
+        chains: Chains,
+        permits: PermitArgs,
+        retry: RetryArgs,
+        simulation: SimulationArgs,
+        fees: FeeArgs,
     ) -> Self {
         Self {
             db,
-            producer,
-            edition_contract,
+            relay,
+            chains,
+            permits,
+            retry,
+            simulation,
+            fees,
         }
     }
 
-    /// Res
+    /// Processes a message, retrying transient failures with backoff until the retry budget
+    /// is spent. hub-nfts is sent a failure event for messages that are given up on.
     ///
     /// # Errors
-    /// This function fails if ...
-    pub async fn process(&self, msg: Services) -> Result<()> {
-        // match topics
-        match msg {
+    /// This function fails if the message failed permanently or ran out of attempts
+    pub async fn process(&self, msg: Services) -> Result<(), Failure> {
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+
+            let error = match self.try_process(msg.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(e) => e,
+            };
+
+            if error.is_retryable() && attempts < self.retry.processor_max_attempts {
+                let delay = self.retry.backoff(attempts);
+
+                warn!(
+                    topic = msg.topic(),
+                    key_id = msg.key_id(),
+                    attempts,
+                    ?delay,
+                    "retrying message: {:?}",
+                    error.chain()
+                );
+
+                tokio::time::sleep(delay).await;
+
+                continue;
+            }
+
+            if let Some(event_name) = event_name(&msg) {
+                if let Err(e) = self.emit_failure(event_key(&msg), event_name, &error).await {
+                    error!(
+                        topic = msg.topic(),
+                        key_id = msg.key_id(),
+                        "failed to emit failure event: {:?}",
+                        e.chain()
+                    );
+                }
+            }
+
+            return Err(Failure { attempts, error });
+        }
+    }
+
+    async fn try_process(&self, msg: Services) -> Result<(), ProcessorError> {
+        let Some(event_name) = event_name(&msg) else {
+            return Ok(());
+        };
+        let span = info_span!(
+            "handle_event",
+            event = event_name,
+            edition_id = field::Empty
+        );
+
+        self.handle(msg, event_name).instrument(span).await
+    }
+
+    /// Runs the handler for `event_name` and records its outcome in one transaction
+    async fn handle(&self, msg: Services, event_name: &'static str) -> Result<(), ProcessorError> {
+        let topic = msg.topic();
+        let key_id = msg.key_id().to_owned();
+        let key = event_key(&msg);
+        let deduplicated = is_deduplicated(&msg);
+
+        let txn = self.db.get().begin().await?;
+
+        if deduplicated {
+            ProcessedEvent::lock(&txn, topic, &key_id, event_name).await?;
+
+            if ProcessedEvent::find(&txn, topic, &key_id, event_name)
+                .await?
+                .is_some()
+            {
+                info!(topic, key_id, event_name, "event already processed");
+
+                return Ok(());
+            }
+        }
+
+        let event = match msg {
             Services::Nfts(key, e) => match e.event {
                 Some(NftEvents::PolygonCreateDrop(payload)) => {
-                    self.create_polygon_edition(key, payload).await
+                    self.create_polygon_edition(&txn, key, payload).await
+                },
+                Some(NftEvents::PolygonRetryDrop(payload)) => {
+                    self.retry_drop(&txn, key, payload).await
+                },
+                Some(NftEvents::PolygonMintDrop(payload)) => {
+                    self.mint_drop(&txn, key, payload).await
+                },
+                Some(NftEvents::PolygonMintDropBatch(payload)) => {
+                    self.mint_drop_batch(&txn, payload).await
+                },
+                Some(NftEvents::PolygonUpdateDrop(payload)) => {
+                    self.update_drop(&txn, key, payload).await
+                },
+                Some(NftEvents::PolygonUpdateRoyalty(payload)) => {
+                    self.update_royalty(&txn, key, payload).await
                 },
-                Some(NftEvents::PolygonRetryDrop(payload)) => self.retry_drop(key, payload).await,
-                Some(NftEvents::PolygonMintDrop(payload)) => self.mint_drop(key, payload).await,
-                Some(NftEvents::PolygonUpdateDrop(payload)) => self.update_drop(key, payload).await,
                 Some(NftEvents::PolygonRetryMintDrop(payload)) => {
-                    self.retry_mint(key, payload).await
+                    self.retry_mint(&txn, key, payload).await
                 },
                 Some(NftEvents::PolygonTransferAsset(payload)) => {
-                    self.sign_permit_token_transfer_hash(key, payload).await
+                    self.sign_permit_token_transfer_hash(&txn, key, payload)
+                        .await
                 },
-                Some(_) | None => Ok(()),
+                Some(NftEvents::PolygonBurnAsset(payload)) => self.burn_asset(&txn, payload).await,
+                Some(_) | None => return Ok(()),
             },
             Services::Treasuries(key, e) => match e.event {
                 Some(TreasuryEvents::PolygonPermitTransferTokenHashSigned(p)) => {
-                    self.send_transfer_asset_txns(key, p).await
+                    self.send_transfer_asset_txns(&txn, key, p).await
                 },
-                Some(_) | None => Ok(()),
+                Some(_) | None => return Ok(()),
             },
+        }?;
+
+        if deduplicated {
+            ProcessedEvent::create(&txn, processed_events::Model {
+                topic: topic.to_owned(),
+                key_id,
+                event: event_name.to_owned(),
+                created_at: Utc::now().naive_utc(),
+            })
+            .await?;
         }
+        Outbox::create(&txn, &key, &event).await?;
+
+        txn.commit().await?;
+        self.relay.notify();
+
+        Ok(())
+    }
+
+    /// Tells hub-nfts that the handler for `event_name` gave up on the message
+    async fn emit_failure(
+        &self,
+        key: PolygonNftEventKey,
+        event_name: &str,
+        error: &ProcessorError,
+    ) -> Result<(), ProcessorError> {
+        let failure = PolygonTransactionFailure {
+            reason: error.reason() as i32,
+            message: error.chain().join(": "),
+        };
+
+        let event = match event_name {
+            "PolygonCreateDrop" => polygon_nft_events::Event::CreateDropFailed(failure),
+            "PolygonRetryDrop" => polygon_nft_events::Event::RetryCreateDropFailed(failure),
+            "PolygonMintDrop" => polygon_nft_events::Event::MintDropFailed(failure),
+            "PolygonMintDropBatch" => polygon_nft_events::Event::MintDropBatchFailed(failure),
+            "PolygonRetryMintDrop" => polygon_nft_events::Event::RetryMintDropFailed(failure),
+            "PolygonUpdateDrop" => polygon_nft_events::Event::UpdateDropFailed(failure),
+            "PolygonUpdateRoyalty" => polygon_nft_events::Event::UpdateRoyaltyFailed(failure),
+            "PolygonTransferAsset" | "PolygonPermitTransferTokenHashSigned" => {
+                polygon_nft_events::Event::TransferAssetFailed(failure)
+            },
+            "PolygonBurnAsset" => polygon_nft_events::Event::BurnAssetFailed(failure),
+            _ => return Ok(()),
+        };
+
+        Outbox::create(self.db.get(), &key, &PolygonNftEvents {
+            event: Some(event),
+        })
+        .await?;
+        self.relay.notify();
+
+        Ok(())
     }
 
     async fn create_polygon_edition(
         &self,
+        txn: &DatabaseTransaction,
         key: NftEventKey,
         payload: CreateEditionTransaction,
-    ) -> Result<()> {
+    ) -> Result<PolygonNftEvents, ProcessorError> {
         let CreateEditionTransaction {
             edition_info,
             fee_receiver,
             fee_numerator,
             amount,
+            network,
             ..
         } = payload;
 
-        let key = PolygonNftEventKey {
-            id: key.id,
-            user_id: key.user_id,
-            project_id: key.project_id,
-        };
-
         let edition_info: proto::EditionInfo =
-            edition_info.context("EditionInfo not found in event payload")?;
-        let edition_id = Collection::find_max_edition_id(&self.db)
-            .await?
-            .unwrap_or(0)
-            + 1;
+            edition_info.ok_or(ProcessorError::MissingField("edition_info"))?;
+        let mut validator = Validator::new();
+        validator.edition_info(&edition_info);
+        let fee_receiver_address = validator.address("fee_receiver", &fee_receiver);
+        let fee_numerator = validator.royalty_bps("fee_numerator", fee_numerator);
+        validator.finish()?;
 
-        let deployer = self.edition_contract.owner().await?;
-        let owner = format!("{deployer:?}");
+        let edition_id = Collection::next_edition_id(txn).await?;
+        Span::current().record("edition_id", edition_id);
 
-        Collection::create(&self.db, collections::Model {
-            id: Uuid::from_str(&key.id)?,
+        let network = self.chains.network(&network).to_owned();
+        let contracts = self.chains.get(&network)?;
+        let Binding {
+            contract, chain_id, ..
+        } = contracts.for_project(&self.db, &key.project_id).await?;
+        let deployer = contracts.owner(contract.address()).await?;
+
+        let typed_tx = contract
+            .create_edition(
+                edition_id.into(),
+                edition_info
+                    .clone()
+                    .try_into()
+                    .map_err(ProcessorError::invalid("edition_info"))?,
+                deployer,
+                amount.into(),
+                fee_receiver_address,
+                fee_numerator.into(),
+            )
+            .tx;
+
+        if let Err(e) = self.simulation.check(&contract, deployer, &typed_tx).await {
+            // the contract may have changed hands since its owner was cached
+            if matches!(e, ProcessorError::Reverted(_)) {
+                contracts.invalidate_owner(contract.address());
+            }
+
+            return Err(e);
+        }
+        let fee_hints = self
+            .fees
+            .hints(&contract, chain_id, deployer, &typed_tx)
+            .await?;
+
+        Collection::create(txn, collections::Model {
+            id: Uuid::from_str(&key.id).map_err(ProcessorError::invalid("key id"))?,
             edition_id,
             fee_receiver: fee_receiver.clone(),
-            owner,
+            owner: format!("{deployer:?}"),
             creator: edition_info.creator.clone(),
             uri: edition_info.uri.clone(),
             name: edition_info.collection.clone(),
             description: edition_info.description.clone(),
             image_uri: edition_info.image_uri.clone(),
             created_at: Utc::now().naive_utc(),
+            contract_address: format!("{:?}", contract.address()),
+            network,
+            supply: Some(
+                amount
+                    .try_into()
+                    .map_err(ProcessorError::invalid("amount"))?,
+            ),
+            minted: 0,
+            fee_numerator: Some(fee_numerator.into()),
         })
         .await?;
 
-        let typed_tx = self
-            .edition_contract
-            .create_edition(
-                edition_id.into(),
-                edition_info.try_into()?,
-                deployer,
-                amount.into(),
-                fee_receiver.parse()?,
-                fee_numerator.try_into()?,
-            )
-            .tx;
-
         if let Some(bytes) = typed_tx.data() {
-            let event = PolygonNftEvents {
+            Ok(PolygonNftEvents {
                 event: Some(polygon_nft_events::Event::SubmitCreateDropTxn(
                     PolygonTransaction {
                         data: bytes.0.to_vec(),
-                        contract_address: format!("{:?}", self.edition_contract.address()),
+                        contract_address: format!("{:?}", contract.address()),
                         edition_id,
+                        chain_id,
+                        fee_hints,
                     },
                 )),
-            };
-
-            self.producer.send(Some(&event), Some(&key)).await?;
+            })
         } else {
-            bail!("No data in transaction")
+            Err(anyhow!("No data in transaction").into())
         }
-
-        Ok(())
     }
 
-    async fn retry_drop(&self, key: NftEventKey, payload: CreateEditionTransaction) -> Result<()> {
+    async fn retry_drop(
+        &self,
+        txn: &DatabaseTransaction,
+        key: NftEventKey,
+        payload: CreateEditionTransaction,
+    ) -> Result<PolygonNftEvents, ProcessorError> {
         let CreateEditionTransaction {
             fee_receiver,
             fee_numerator,
@@ -152,147 +361,356 @@ impl Processor {
             ..
         } = payload;
 
-        let collection = Collection::find_by_id(&self.db, key.id.parse()?)
-            .await?
-            .context(format!("No collection found for id {:?}", key.id))?;
+        let mut validator = Validator::new();
+        let fee_receiver = validator.address("fee_receiver", &fee_receiver);
+        let fee_numerator = validator.royalty_bps("fee_numerator", fee_numerator);
+        validator.finish()?;
+
+        let collection = Collection::find_by_id(
+            txn,
+            key.id.parse().map_err(ProcessorError::invalid("key id"))?,
+        )
+        .await?
+        .ok_or(ProcessorError::NotFound("collection"))?;
+        Span::current().record("edition_id", collection.edition_id);
+        let Binding {
+            contract, chain_id, ..
+        } = self.chains.for_collection(&collection)?;
 
         let edition_info = EditionInfo {
             description: collection.description,
             image_uri: collection.image_uri,
             collection: collection.name,
             uri: collection.uri,
-            creator: collection.creator.parse()?,
+            creator: collection
+                .creator
+                .parse()
+                .map_err(ProcessorError::invalid("creator"))?,
         };
 
-        let typed_tx = self
-            .edition_contract
+        let owner: Address = collection
+            .owner
+            .parse()
+            .map_err(ProcessorError::invalid("owner"))?;
+
+        let typed_tx = contract
             .create_edition(
                 collection.edition_id.into(),
                 edition_info,
-                collection.owner.parse()?,
+                owner,
                 amount.into(),
-                fee_receiver.parse()?,
-                fee_numerator.try_into()?,
+                fee_receiver,
+                fee_numerator.into(),
             )
             .tx;
 
+        let fee_hints = self
+            .fees
+            .hints(&contract, chain_id, owner, &typed_tx)
+            .await?;
+
         if let Some(bytes) = typed_tx.data() {
-            let event = PolygonNftEvents {
+            Ok(PolygonNftEvents {
                 event: Some(polygon_nft_events::Event::SubmitRetryCreateDropTxn(
                     PolygonTransaction {
                         data: bytes.0.to_vec(),
-                        contract_address: format!("{:?}", self.edition_contract.address()),
+                        contract_address: format!("{:?}", contract.address()),
                         edition_id: collection.edition_id,
+                        chain_id,
+                        fee_hints,
                     },
                 )),
-            };
-
-            self.producer.send(Some(&event), Some(&key.into())).await?;
+            })
         } else {
-            bail!("No data in transaction")
+            Err(anyhow!("No data in transaction").into())
         }
+    }
 
-        Ok(())
+    /// Persists every mint of the batch at once and groups them into one
+    /// `safeBatchTransferFrom` call per network, contract, sender and receiver
+    async fn mint_drop_batch(
+        &self,
+        txn: &DatabaseTransaction,
+        payload: MintEditionBatchTransaction,
+    ) -> Result<PolygonNftEvents, ProcessorError> {
+        let MintEditionBatchTransaction { mints } = payload;
+
+        if mints.is_empty() {
+            return Err(ProcessorError::MissingField("mints"));
+        }
+
+        let now = Utc::now().naive_utc();
+        let mut drops = HashMap::new();
+        let mut models = Vec::with_capacity(mints.len());
+        let mut batches: BTreeMap<(String, Address, Address, Address), Vec<(String, U256, U256)>> =
+            BTreeMap::new();
+
+        for mint in mints {
+            let collection_id: Uuid = mint
+                .collection_id
+                .parse()
+                .map_err(ProcessorError::invalid("collection_id"))?;
+
+            if !drops.contains_key(&collection_id) {
+                let collection = Collection::find_by_id(txn, collection_id)
+                    .await?
+                    .ok_or(ProcessorError::NotFound("collection"))?;
+
+                drops.insert(collection_id, collection);
+            }
+
+            let collection: &collections::Model = &drops[&collection_id];
+            let contract = self.chains.for_collection(collection)?.contract;
+
+            let sender: Address = collection
+                .owner
+                .parse()
+                .map_err(ProcessorError::invalid("owner"))?;
+            let receiver: Address = mint
+                .receiver
+                .parse()
+                .map_err(ProcessorError::invalid("receiver"))?;
+
+            models.push(mints::Model {
+                id: mint
+                    .mint_id
+                    .parse()
+                    .map_err(ProcessorError::invalid("mint_id"))?,
+                collection_id,
+                owner: mint.receiver,
+                amount: mint
+                    .amount
+                    .try_into()
+                    .map_err(ProcessorError::invalid("amount"))?,
+                created_at: now,
+                burned_at: None,
+                network: collection.network.clone(),
+            });
+
+            batches
+                .entry((
+                    collection.network.clone(),
+                    contract.address(),
+                    sender,
+                    receiver,
+                ))
+                .or_default()
+                .push((
+                    mint.mint_id,
+                    collection.edition_id.into(),
+                    mint.amount.into(),
+                ));
+        }
+
+        let mut transactions = Vec::with_capacity(batches.len());
+
+        for ((network, contract_address, sender, receiver), mints) in batches {
+            let (mint_ids, (ids, amounts)): (Vec<_>, (Vec<_>, Vec<_>)) = mints
+                .into_iter()
+                .map(|(mint_id, id, amount)| (mint_id, (id, amount)))
+                .unzip();
+
+            let Binding {
+                contract, chain_id, ..
+            } = self.chains.get(&network)?.get(contract_address);
+
+            let typed_tx = contract
+                .safe_batch_transfer_from(sender, receiver, ids, amounts, Bytes::new())
+                .tx;
+
+            let fee_hints = self
+                .fees
+                .hints(&contract, chain_id, sender, &typed_tx)
+                .await?;
+
+            let data = typed_tx
+                .data()
+                .ok_or_else(|| anyhow!("No data in transaction"))?
+                .0
+                .to_vec();
+
+            transactions.push(PolygonBatchMintTransaction {
+                data,
+                contract_address: format!("{contract_address:?}"),
+                receiver: format!("{receiver:?}"),
+                mint_ids,
+                chain_id,
+                fee_hints,
+            });
+        }
+
+        if !Mint::create_many(txn, models).await? {
+            return Err(ProcessorError::SupplyExhausted);
+        }
+
+        Ok(PolygonNftEvents {
+            event: Some(polygon_nft_events::Event::SubmitMintDropBatchTxns(
+                PolygonBatchMintTransactions { transactions },
+            )),
+        })
     }
 
-    async fn retry_mint(&self, key: NftEventKey, payload: MintEditionTransaction) -> Result<()> {
+    async fn retry_mint(
+        &self,
+        txn: &DatabaseTransaction,
+        key: NftEventKey,
+        payload: MintEditionTransaction,
+    ) -> Result<PolygonNftEvents, ProcessorError> {
         let MintEditionTransaction {
             receiver,
             amount,
             collection_id,
         } = payload;
 
-        let collection = Collection::find_by_id(&self.db, collection_id.parse()?)
-            .await?
-            .context("collection not found")?;
+        let collection = Collection::find_by_id(
+            txn,
+            collection_id
+                .parse()
+                .map_err(ProcessorError::invalid("collection_id"))?,
+        )
+        .await?
+        .ok_or(ProcessorError::NotFound("collection"))?;
+        Span::current().record("edition_id", collection.edition_id);
+        let Binding {
+            contract, chain_id, ..
+        } = self.chains.for_collection(&collection)?;
 
-        Mint::find_by_id(&self.db, key.id.parse()?)
-            .await?
-            .context("mint not found")?;
+        Mint::find_by_id(
+            txn,
+            key.id.parse().map_err(ProcessorError::invalid("key id"))?,
+        )
+        .await?
+        .ok_or(ProcessorError::NotFound("mint"))?;
+
+        let sender: Address = collection
+            .owner
+            .parse()
+            .map_err(ProcessorError::invalid("owner"))?;
 
-        let typed_tx = self
-            .edition_contract
+        let typed_tx = contract
             .safe_transfer_from(
-                collection.owner.parse()?,
-                receiver.parse()?,
+                sender,
+                receiver
+                    .parse()
+                    .map_err(ProcessorError::invalid("receiver"))?,
                 collection.edition_id.into(),
                 amount.into(),
                 Bytes::new(),
             )
             .tx;
 
+        let fee_hints = self
+            .fees
+            .hints(&contract, chain_id, sender, &typed_tx)
+            .await?;
+
         if let Some(bytes) = typed_tx.data() {
-            let event = PolygonNftEvents {
+            Ok(PolygonNftEvents {
                 event: Some(polygon_nft_events::Event::SubmitRetryMintDropTxn(
                     PolygonTransaction {
                         data: bytes.0.to_vec(),
-                        contract_address: format!("{:?}", self.edition_contract.address()),
+                        contract_address: format!("{:?}", contract.address()),
                         edition_id: collection.edition_id,
+                        chain_id,
+                        fee_hints,
                     },
                 )),
-            };
-
-            self.producer.send(Some(&event), Some(&key.into())).await?;
+            })
         } else {
-            bail!("No data in transaction")
+            Err(anyhow!("No data in transaction").into())
         }
-
-        Ok(())
     }
 
-    async fn mint_drop(&self, key: NftEventKey, payload: MintEditionTransaction) -> Result<()> {
+    async fn mint_drop(
+        &self,
+        txn: &DatabaseTransaction,
+        key: NftEventKey,
+        payload: MintEditionTransaction,
+    ) -> Result<PolygonNftEvents, ProcessorError> {
         let MintEditionTransaction {
             receiver,
             amount,
             collection_id,
         } = payload;
 
-        let collection = Collection::find_by_id(&self.db, collection_id.parse()?)
-            .await?
-            .context(format!("No collection found for id {:?}", key.id))?;
-        Mint::create(&self.db, mints::Model {
-            id: key.id.parse()?,
-            collection_id: collection.id,
-            owner: receiver.parse()?,
-            amount: amount.try_into()?,
-            created_at: Utc::now().naive_utc(),
-        })
-        .await?;
+        let collection = Collection::find_by_id(
+            txn,
+            collection_id
+                .parse()
+                .map_err(ProcessorError::invalid("collection_id"))?,
+        )
+        .await?
+        .ok_or(ProcessorError::NotFound("collection"))?;
+        Span::current().record("edition_id", collection.edition_id);
+        let Binding {
+            contract, chain_id, ..
+        } = self.chains.for_collection(&collection)?;
 
-        let typed_tx = self
-            .edition_contract
+        let sender: Address = collection
+            .owner
+            .parse()
+            .map_err(ProcessorError::invalid("owner"))?;
+
+        let typed_tx = contract
             .safe_transfer_from(
-                collection.owner.parse()?,
-                receiver.parse()?,
+                sender,
+                receiver
+                    .parse()
+                    .map_err(ProcessorError::invalid("receiver"))?,
                 collection.edition_id.into(),
                 amount.into(),
                 Bytes::new(),
             )
             .tx;
 
+        self.simulation.check(&contract, sender, &typed_tx).await?;
+        let fee_hints = self
+            .fees
+            .hints(&contract, chain_id, sender, &typed_tx)
+            .await?;
+
+        Mint::create_within_supply(txn, mints::Model {
+            id: key.id.parse().map_err(ProcessorError::invalid("key id"))?,
+            collection_id: collection.id,
+            owner: receiver.clone(),
+            amount: amount
+                .try_into()
+                .map_err(ProcessorError::invalid("amount"))?,
+            created_at: Utc::now().naive_utc(),
+            burned_at: None,
+            network: collection.network.clone(),
+        })
+        .await?
+        .ok_or(ProcessorError::SupplyExhausted)?;
+
         if let Some(bytes) = typed_tx.data() {
-            let event = PolygonNftEvents {
+            Ok(PolygonNftEvents {
                 event: Some(polygon_nft_events::Event::SubmitMintDropTxn(
                     PolygonTransaction {
                         data: bytes.0.to_vec(),
-                        contract_address: format!("{:?}", self.edition_contract.address()),
+                        contract_address: format!("{:?}", contract.address()),
                         edition_id: collection.edition_id,
+                        chain_id,
+                        fee_hints,
                     },
                 )),
-            };
-
-            self.producer.send(Some(&event), Some(&key.into())).await?;
+            })
         } else {
-            bail!("No data in transaction")
+            Err(anyhow!("No data in transaction").into())
         }
-
-        Ok(())
     }
 
-    async fn update_drop(&self, key: NftEventKey, payload: UpdateEdtionTransaction) -> Result<()> {
+    async fn update_drop(
+        &self,
+        txn: &DatabaseTransaction,
+        key: NftEventKey,
+        payload: UpdateEdtionTransaction,
+    ) -> Result<PolygonNftEvents, ProcessorError> {
         let UpdateEdtionTransaction { edition_info } = payload;
 
-        let edition_info = edition_info.context("EditionInfo not found in event payload")?;
+        let edition_info = edition_info.ok_or(ProcessorError::MissingField("edition_info"))?;
+        Validator::new().edition_info(&edition_info).finish()?;
+
         let proto::EditionInfo {
             description,
             image_uri,
@@ -302,47 +720,139 @@ impl Processor {
             ..
         } = edition_info.clone();
 
-        let collection_model = Collection::find_by_id(&self.db, key.id.parse()?)
-            .await?
-            .context("collection not found")?;
+        let collection_model = Collection::find_by_id(
+            txn,
+            key.id.parse().map_err(ProcessorError::invalid("key id"))?,
+        )
+        .await?
+        .ok_or(ProcessorError::NotFound("collection"))?;
+        Span::current().record("edition_id", collection_model.edition_id);
+        let Binding {
+            contract, chain_id, ..
+        } = self.chains.for_collection(&collection_model)?;
 
-        let mut collection_am = Collection::get_active_model(collection_model.clone());
+        let typed_tx = contract
+            .edit_edition(
+                collection_model.edition_id.into(),
+                edition_info
+                    .try_into()
+                    .map_err(ProcessorError::invalid("edition_info"))?,
+            )
+            .tx;
+
+        let fee_hints = self
+            .fees
+            .hints(
+                &contract,
+                chain_id,
+                collection_model
+                    .owner
+                    .parse()
+                    .map_err(ProcessorError::invalid("owner"))?,
+                &typed_tx,
+            )
+            .await?;
+
+        let data = typed_tx
+            .data()
+            .ok_or_else(|| anyhow!("No data in transaction"))?
+            .0
+            .to_vec();
+
+        let edition_id = collection_model.edition_id;
+        let mut collection_am = Collection::get_active_model(collection_model);
         collection_am.description = Set(description);
         collection_am.name = Set(collection);
         collection_am.image_uri = Set(image_uri);
         collection_am.uri = Set(uri);
         collection_am.creator = Set(creator);
-        Collection::update(&self.db, collection_am).await?;
+        Collection::update(txn, collection_am).await?;
+
+        Ok(PolygonNftEvents {
+            event: Some(polygon_nft_events::Event::SubmitUpdateDropTxn(
+                PolygonTransaction {
+                    data,
+                    contract_address: format!("{:?}", contract.address()),
+                    edition_id,
+                    chain_id,
+                    fee_hints,
+                },
+            )),
+        })
+    }
+
+    async fn update_royalty(
+        &self,
+        txn: &DatabaseTransaction,
+        key: NftEventKey,
+        payload: UpdatePolygonRoyalty,
+    ) -> Result<PolygonNftEvents, ProcessorError> {
+        let UpdatePolygonRoyalty {
+            fee_receiver,
+            fee_numerator,
+        } = payload;
 
-        let typed_tx = self
-            .edition_contract
-            .edit_edition(collection_model.edition_id.into(), edition_info.try_into()?)
+        let mut validator = Validator::new();
+        let receiver = validator.address("fee_receiver", &fee_receiver);
+        let fee_numerator = validator.royalty_bps("fee_numerator", fee_numerator);
+        validator.finish()?;
+
+        let collection = Collection::find_by_id(
+            txn,
+            key.id.parse().map_err(ProcessorError::invalid("key id"))?,
+        )
+        .await?
+        .ok_or(ProcessorError::NotFound("collection"))?;
+        Span::current().record("edition_id", collection.edition_id);
+        let Binding {
+            contract, chain_id, ..
+        } = self.chains.for_collection(&collection)?;
+        let owner: Address = collection
+            .owner
+            .parse()
+            .map_err(ProcessorError::invalid("owner"))?;
+
+        let typed_tx = contract
+            .set_token_royalty(collection.edition_id.into(), receiver, fee_numerator.into())
             .tx;
 
-        if let Some(bytes) = typed_tx.data() {
-            let event = PolygonNftEvents {
-                event: Some(polygon_nft_events::Event::SubmitUpdateDropTxn(
-                    PolygonTransaction {
-                        data: bytes.0.to_vec(),
-                        contract_address: format!("{:?}", self.edition_contract.address()),
-                        edition_id: collection_model.edition_id,
-                    },
-                )),
-            };
+        self.simulation.check(&contract, owner, &typed_tx).await?;
+        let fee_hints = self
+            .fees
+            .hints(&contract, chain_id, owner, &typed_tx)
+            .await?;
 
-            self.producer.send(Some(&event), Some(&key.into())).await?;
-        } else {
-            bail!("No data in transaction")
-        }
+        let data = typed_tx
+            .data()
+            .ok_or_else(|| anyhow!("No data in transaction"))?
+            .0
+            .to_vec();
 
-        Ok(())
+        let edition_id = collection.edition_id;
+        let mut collection_am = Collection::get_active_model(collection);
+        collection_am.fee_receiver = Set(fee_receiver);
+        collection_am.fee_numerator = Set(Some(fee_numerator.into()));
+        Collection::update(txn, collection_am).await?;
+
+        Ok(PolygonNftEvents {
+            event: Some(polygon_nft_events::Event::SubmitUpdateRoyaltyTxn(
+                PolygonTransaction {
+                    data,
+                    contract_address: format!("{:?}", contract.address()),
+                    edition_id,
+                    chain_id,
+                    fee_hints,
+                },
+            )),
+        })
     }
 
     async fn sign_permit_token_transfer_hash(
         &self,
+        txn: &DatabaseTransaction,
         key: NftEventKey,
         payload: TransferPolygonAsset,
-    ) -> Result<()> {
+    ) -> Result<PolygonNftEvents, ProcessorError> {
         let TransferPolygonAsset {
             collection_mint_id,
             owner_address,
@@ -350,27 +860,61 @@ impl Processor {
             amount,
         } = payload;
 
-        let (_, collection) =
-            Mint::find_with_collection(&self.db, collection_mint_id.parse()?).await?;
+        let collection_mint_id = collection_mint_id
+            .parse()
+            .map_err(ProcessorError::invalid("collection_mint_id"))?;
+        let (mint, collection) = Mint::find_with_collection(txn, collection_mint_id)
+            .await?
+            .ok_or(ProcessorError::NotFound("mint"))?;
 
-        let collection = collection.context("No collection found")?;
+        if mint.burned_at.is_some() {
+            return Err(ProcessorError::AlreadyBurned);
+        }
 
-        let hash = self
-            .edition_contract
-            .get_hash_typed_data_v4(
-                owner_address.parse()?,
-                collection.owner.parse()?,
-                collection.edition_id.into(),
-                amount.into(),
-                U256::MAX,
-            )
-            .await
-            .context("failed to get hash of the data")?;
+        let collection = collection.ok_or(ProcessorError::NotFound("collection"))?;
+        Span::current().record("edition_id", collection.edition_id);
+        let binding = self.chains.for_collection(&collection)?;
+
+        let now = Utc::now().naive_utc();
+        let deadline = self.permits.deadline(now);
+        let permit = Permit {
+            owner: owner_address
+                .parse()
+                .map_err(ProcessorError::invalid("owner_address"))?,
+            spender: collection
+                .owner
+                .parse()
+                .map_err(ProcessorError::invalid("owner"))?,
+            token_id: collection.edition_id.into(),
+            value: amount.into(),
+            deadline: deadline.into(),
+        };
+        let hash = binding.permit_domain.hash(&permit);
+
+        if self.permits.verify_permit_hashes {
+            binding.verify_permit_hash(&permit).await?;
+        }
+
+        PendingTransfer::create(txn, pending_transfers::Model {
+            id: key.id.parse().map_err(ProcessorError::invalid("key id"))?,
+            collection_mint_id,
+            owner: owner_address.clone(),
+            spender: collection.owner.clone(),
+            recipient: recipient_address.clone(),
+            edition_id: collection.edition_id,
+            amount: amount
+                .try_into()
+                .map_err(ProcessorError::invalid("amount"))?,
+            deadline,
+            hash: hash.as_bytes().to_vec(),
+            created_at: now,
+        })
+        .await?;
 
         let event = PolygonNftEvents {
             event: Some(polygon_nft_events::Event::SignPermitTokenTransferHash(
                 PermitArgsHash {
-                    data: hash.to_vec(),
+                    data: hash.as_bytes().to_vec(),
                     owner: owner_address,
                     spender: collection.owner,
                     recipient: recipient_address,
@@ -380,16 +924,81 @@ impl Processor {
             )),
         };
 
-        self.producer.send(Some(&event), Some(&key.into())).await?;
+        Ok(event)
+    }
 
-        Ok(())
+    /// Builds the burn transaction of a mint. The mint is only marked burned by the indexer
+    /// once the burn is confirmed on-chain, so a failed burn can be retried.
+    async fn burn_asset(
+        &self,
+        txn: &DatabaseTransaction,
+        payload: BurnPolygonAsset,
+    ) -> Result<PolygonNftEvents, ProcessorError> {
+        let BurnPolygonAsset {
+            collection_mint_id,
+            owner_address,
+        } = payload;
+
+        let (mint, collection) = Mint::find_with_collection(
+            txn,
+            collection_mint_id
+                .parse()
+                .map_err(ProcessorError::invalid("collection_mint_id"))?,
+        )
+        .await?
+        .ok_or(ProcessorError::NotFound("mint"))?;
+        let collection = collection.ok_or(ProcessorError::NotFound("collection"))?;
+        Span::current().record("edition_id", collection.edition_id);
+        let Binding {
+            contract, chain_id, ..
+        } = self.chains.for_collection(&collection)?;
+
+        if mint.burned_at.is_some() {
+            return Err(ProcessorError::AlreadyBurned);
+        }
+
+        let owner: Address = owner_address
+            .parse()
+            .map_err(ProcessorError::invalid("owner_address"))?;
+
+        if !same_address(&mint.owner, owner) {
+            return Err(ProcessorError::NotOwner);
+        }
+
+        let typed_tx = contract
+            .burn(owner, collection.edition_id.into(), mint.amount.into())
+            .tx;
+
+        let fee_hints = self
+            .fees
+            .hints(&contract, chain_id, owner, &typed_tx)
+            .await?;
+
+        let data = typed_tx
+            .data()
+            .ok_or_else(|| anyhow!("No data in transaction"))?
+            .0
+            .to_vec();
+
+        Ok(PolygonNftEvents {
+            event: Some(polygon_nft_events::Event::SubmitBurnAssetTxn(
+                PolygonTransaction {
+                    data,
+                    contract_address: format!("{:?}", contract.address()),
+                    edition_id: collection.edition_id,
+                    chain_id,
+                    fee_hints,
+                },
+            )),
+        })
     }
 
     async fn send_transfer_asset_txns(
         &self,
+        txn: &DatabaseTransaction,
         key: TreasuryEventKey,
         payload: PolygonPermitHashSignature,
-    ) -> Result<()> {
+    ) -> Result<PolygonNftEvents, ProcessorError> {
         let PolygonPermitHashSignature {
             signature,
             owner,
@@ -399,29 +1008,103 @@ impl Processor {
             amount,
         } = payload;
 
-        let EcdsaSignature { r, s, v } = signature.context("No ECDSA Signature found")?;
+        let EcdsaSignature { r, s, v } =
+            signature.ok_or(ProcessorError::MissingField("signature"))?;
+
+        let pending_transfer = PendingTransfer::find_by_id(
+            txn,
+            key.id.parse().map_err(ProcessorError::invalid("key id"))?,
+        )
+        .await?
+        .ok_or(ProcessorError::NotFound("pending transfer"))?;
+
+        if pending_transfer.submitted_at.is_some() {
+            return Err(ProcessorError::PermitReplayed);
+        }
+
+        let (_, collection) = Mint::find_with_collection(txn, pending_transfer.collection_mint_id)
+            .await?
+            .ok_or(ProcessorError::NotFound("mint"))?;
+        let collection = collection.ok_or(ProcessorError::NotFound("collection"))?;
+        Span::current().record("edition_id", collection.edition_id);
+        let Binding {
+            contract, chain_id, ..
+        } = self.chains.for_collection(&collection)?;
+
+        let now = Utc::now().naive_utc();
+
+        if now.timestamp() >= pending_transfer.deadline {
+            return Err(ProcessorError::PermitExpired(pending_transfer.deadline));
+        }
+
+        let owner: Address = owner.parse().map_err(ProcessorError::invalid("owner"))?;
+        let spender: Address = spender
+            .parse()
+            .map_err(ProcessorError::invalid("spender"))?;
+        let recipient: Address = recipient
+            .parse()
+            .map_err(ProcessorError::invalid("recipient"))?;
+
+        if !same_address(&pending_transfer.owner, owner) {
+            return Err(ProcessorError::PermitMismatch("owner"));
+        }
+
+        if !same_address(&pending_transfer.spender, spender) {
+            return Err(ProcessorError::PermitMismatch("spender"));
+        }
+
+        if !same_address(&pending_transfer.recipient, recipient) {
+            return Err(ProcessorError::PermitMismatch("recipient"));
+        }
+
+        if edition_id != pending_transfer.edition_id {
+            return Err(ProcessorError::PermitMismatch("edition_id"));
+        }
+
+        if i64::try_from(amount).ok() != Some(pending_transfer.amount) {
+            return Err(ProcessorError::PermitMismatch("amount"));
+        }
+
+        let v = normalize_v(
+            v.try_into()
+                .map_err(ProcessorError::invalid("signature v"))?,
+        );
+        let r: [u8; 32] = r
+            .try_into()
+            .map_err(|_| ProcessorError::invalid("signature r")("expected 32 bytes"))?;
+        let s: [u8; 32] = s
+            .try_into()
+            .map_err(|_| ProcessorError::invalid("signature s")("expected 32 bytes"))?;
+        let hash: [u8; 32] = pending_transfer
+            .hash
+            .as_slice()
+            .try_into()
+            .map_err(|_| anyhow!("stored permit hash is not 32 bytes"))?;
+
+        let signer =
+            recover_signer(hash.into(), r, s, v).map_err(ProcessorError::InvalidSignature)?;
+
+        if signer != owner {
+            return Err(ProcessorError::PermitMismatch("signer"));
+        }
 
-        let permit_tx = self
-            .edition_contract
+        let permit_tx = contract
             .permit(
-                owner.parse()?,
-                spender.parse()?,
+                owner,
+                spender,
                 edition_id.into(),
                 amount.into(),
-                U256::MAX,
-                v.try_into()?,
-                r.try_into()
-                    .map_err(|_| anyhow!("failed to parse r component of ECDSA"))?,
-                s.try_into()
-                    .map_err(|_| anyhow!("failed to parse s component of ECDSA"))?,
+                pending_transfer.deadline.into(),
+                v,
+                r,
+                s,
             )
             .tx;
 
-        let safe_transfer_from = self
-            .edition_contract
+        let safe_transfer_from = contract
             .safe_transfer_from(
-                owner.parse()?,
-                recipient.parse()?,
+                owner,
+                recipient,
                 edition_id.into(),
                 amount.into(),
                 Bytes::new(),
@@ -433,25 +1116,184 @@ impl Processor {
             .data()
             .context("No data in safe transfer from tx")?;
 
+        self.simulation
+            .check(&contract, spender, &permit_tx)
+            .await?;
+        let permit_fee_hints = self
+            .fees
+            .hints(&contract, chain_id, spender, &permit_tx)
+            .await?;
+        let transfer_fee_hints = self.fees.fees_only(&contract, chain_id).await?;
+
+        let mut pending_transfer_am: pending_transfers::ActiveModel = pending_transfer.into();
+        pending_transfer_am.submitted_at = Set(Some(now));
+        PendingTransfer::update(txn, pending_transfer_am).await?;
+
         let event = PolygonNftEvents {
             event: Some(polygon_nft_events::Event::SubmitTransferAssetTxns(
                 PolygonTokenTransferTxns {
                     permit_token_transfer_txn: Some(PolygonTransaction {
                         data: permit_tx_data.0.to_vec(),
-                        contract_address: format!("{:?}", self.edition_contract.address()),
+                        contract_address: format!("{:?}", contract.address()),
                         edition_id,
+                        chain_id,
+                        fee_hints: permit_fee_hints,
                     }),
                     safe_transfer_from_txn: Some(PolygonTransaction {
                         data: safe_transfer_from_data.0.to_vec(),
-                        contract_address: format!("{:?}", self.edition_contract.address()),
+                        contract_address: format!("{:?}", contract.address()),
                         edition_id,
+                        chain_id,
+                        fee_hints: transfer_fee_hints,
                     }),
                 },
             )),
         };
 
-        self.producer.send(Some(&event), Some(&key.into())).await?;
+        Ok(event)
+    }
+}
 
-        Ok(())
+/// The name of the handled event variant a message carries, used as part of the
+/// processed events ledger key
+#[must_use]
+pub fn event_name(msg: &Services) -> Option<&'static str> {
+    match msg {
+        Services::Nfts(_, e) => match e.event {
+            Some(NftEvents::PolygonCreateDrop(_)) => Some("PolygonCreateDrop"),
+            Some(NftEvents::PolygonRetryDrop(_)) => Some("PolygonRetryDrop"),
+            Some(NftEvents::PolygonMintDrop(_)) => Some("PolygonMintDrop"),
+            Some(NftEvents::PolygonMintDropBatch(_)) => Some("PolygonMintDropBatch"),
+            Some(NftEvents::PolygonUpdateDrop(_)) => Some("PolygonUpdateDrop"),
+            Some(NftEvents::PolygonUpdateRoyalty(_)) => Some("PolygonUpdateRoyalty"),
+            Some(NftEvents::PolygonRetryMintDrop(_)) => Some("PolygonRetryMintDrop"),
+            Some(NftEvents::PolygonTransferAsset(_)) => Some("PolygonTransferAsset"),
+            Some(NftEvents::PolygonBurnAsset(_)) => Some("PolygonBurnAsset"),
+            Some(_) | None => None,
+        },
+        Services::Treasuries(_, e) => match e.event {
+            Some(TreasuryEvents::PolygonPermitTransferTokenHashSigned(_)) => {
+                Some("PolygonPermitTransferTokenHashSigned")
+            },
+            Some(_) | None => None,
+        },
+    }
+}
+
+/// Whether the key id of `msg` is unique to a single request, so a redelivery of it can
+/// be recognised by the processed events ledger.
+///
+/// Updates and retries are keyed by the drop or mint they act on and are meant to repeat,
+/// so they are always processed and never recorded.
+fn is_deduplicated(msg: &Services) -> bool {
+    match msg {
+        Services::Nfts(_, e) => matches!(
+            e.event,
+            Some(
+                NftEvents::PolygonCreateDrop(_)
+                    | NftEvents::PolygonMintDrop(_)
+                    | NftEvents::PolygonMintDropBatch(_)
+                    | NftEvents::PolygonTransferAsset(_)
+                    | NftEvents::PolygonBurnAsset(_)
+            )
+        ),
+        Services::Treasuries(..) => true,
+    }
+}
+
+/// Compares an address stored in the database with one received in an event, ignoring the
+/// difference in letter case between checksummed and lowercase hex
+fn same_address(stored: &str, received: Address) -> bool {
+    stored
+        .parse::<Address>()
+        .map_or(false, |stored| stored == received)
+}
+
+/// The key of the events emitted in response to `msg`
+#[must_use]
+pub fn event_key(msg: &Services) -> PolygonNftEventKey {
+    match msg {
+        Services::Nfts(key, _) => key.clone().into(),
+        Services::Treasuries(key, _) => key.clone().into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DROP_ID: &str = "6f4c1ef7-8d2f-4ab8-a4a3-0b0a8e1e3a10";
+
+    fn nft_message(event: NftEvents) -> Services {
+        Services::Nfts(
+            NftEventKey {
+                id: DROP_ID.to_owned(),
+                ..NftEventKey::default()
+            },
+            proto::NftEvents { event: Some(event) },
+        )
+    }
+
+    #[test]
+    fn repeated_drop_updates_are_all_processed() {
+        let first = nft_message(NftEvents::PolygonUpdateDrop(UpdateEdtionTransaction {
+            edition_info: Some(proto::EditionInfo {
+                collection: "First".to_owned(),
+                ..proto::EditionInfo::default()
+            }),
+        }));
+        let second = nft_message(NftEvents::PolygonUpdateDrop(UpdateEdtionTransaction {
+            edition_info: Some(proto::EditionInfo {
+                collection: "Second".to_owned(),
+                ..proto::EditionInfo::default()
+            }),
+        }));
+
+        assert_eq!(first.key_id(), second.key_id());
+        assert_eq!(event_name(&first), event_name(&second));
+        assert!(!is_deduplicated(&first));
+        assert!(!is_deduplicated(&second));
+    }
+
+    #[test]
+    fn repeated_royalty_updates_are_all_processed() {
+        let first = nft_message(NftEvents::PolygonUpdateRoyalty(UpdatePolygonRoyalty {
+            fee_receiver: "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".to_owned(),
+            fee_numerator: 500,
+        }));
+        let second = nft_message(NftEvents::PolygonUpdateRoyalty(UpdatePolygonRoyalty {
+            fee_receiver: "0x70997970C51812dc3A010C7d01b50e0d17dc79C8".to_owned(),
+            fee_numerator: 500,
+        }));
+
+        assert_eq!(first.key_id(), second.key_id());
+        assert!(!is_deduplicated(&first));
+        assert!(!is_deduplicated(&second));
+    }
+
+    #[test]
+    fn retries_are_not_deduplicated() {
+        let retry_drop = nft_message(NftEvents::PolygonRetryDrop(
+            CreateEditionTransaction::default(),
+        ));
+        let retry_mint = nft_message(NftEvents::PolygonRetryMintDrop(
+            MintEditionTransaction::default(),
+        ));
+
+        assert!(!is_deduplicated(&retry_drop));
+        assert!(!is_deduplicated(&retry_mint));
+    }
+
+    #[test]
+    fn requests_with_unique_keys_are_deduplicated() {
+        let create = nft_message(NftEvents::PolygonCreateDrop(
+            CreateEditionTransaction::default(),
+        ));
+        let mint = nft_message(NftEvents::PolygonMintDrop(MintEditionTransaction::default()));
+        let burn = nft_message(NftEvents::PolygonBurnAsset(BurnPolygonAsset::default()));
+
+        assert!(is_deduplicated(&create));
+        assert!(is_deduplicated(&mint));
+        assert!(is_deduplicated(&burn));
     }
 }
@@ -0,0 +1,107 @@
+use ethers::{
+    middleware::gas_oracle::{GasOracle, Polygon},
+    providers::Middleware,
+    types::{transaction::eip2718::TypedTransaction, Address, Chain, U256},
+};
+use holaplex_hub_nfts_polygon_core::{metrics::time_rpc, proto::PolygonFeeHints, ProcessorError};
+use hub_core::{clap, prelude::*};
+
+use crate::{simulation::call_error, EditionContract};
+
+/// Arguments controlling the gas and fee hints attached to emitted transactions.
+/// Multipliers are percentages applied to the estimates.
+#[derive(Debug, Clone, Copy, clap::Args)]
+pub struct FeeArgs {
+    #[arg(long, env, default_value_t = false)]
+    pub attach_fee_hints: bool,
+    #[arg(long, env, default_value_t = 120)]
+    pub gas_limit_multiplier_pct: u64,
+    #[arg(long, env, default_value_t = 150)]
+    pub max_fee_multiplier_pct: u64,
+    #[arg(long, env, default_value_t = 120)]
+    pub priority_fee_multiplier_pct: u64,
+}
+
+impl FeeArgs {
+    /// Estimates the gas limit of `tx` when submitted by `from` along with the current
+    /// EIP-1559 fees of the chain. Returns `None` unless fee hints are enabled.
+    ///
+    /// # Errors
+    /// This function fails if the transaction reverts or an estimate cannot be fetched
+    pub async fn hints(
+        &self,
+        contract: &EditionContract,
+        chain_id: u64,
+        from: Address,
+        tx: &TypedTransaction,
+    ) -> Result<Option<PolygonFeeHints>, ProcessorError> {
+        if !self.attach_fee_hints {
+            return Ok(None);
+        }
+
+        let mut tx = tx.clone();
+        tx.set_from(from);
+
+        let gas_limit = time_rpc("eth_estimateGas", contract.client().estimate_gas(&tx, None))
+            .await
+            .map_err(call_error)?;
+
+        self.fee_hints(contract, chain_id, Some(gas_limit)).await
+    }
+
+    /// Fee hints for a transaction that can only be estimated once an earlier transaction
+    /// has landed. The gas limit is left at zero for the treasury to estimate.
+    ///
+    /// # Errors
+    /// This function fails if the fees cannot be fetched
+    pub async fn fees_only(
+        &self,
+        contract: &EditionContract,
+        chain_id: u64,
+    ) -> Result<Option<PolygonFeeHints>, ProcessorError> {
+        if !self.attach_fee_hints {
+            return Ok(None);
+        }
+
+        self.fee_hints(contract, chain_id, None).await
+    }
+
+    async fn fee_hints(
+        &self,
+        contract: &EditionContract,
+        chain_id: u64,
+        gas_limit: Option<U256>,
+    ) -> Result<Option<PolygonFeeHints>, ProcessorError> {
+        let (max_fee, priority_fee) = match Chain::try_from(chain_id) {
+            Ok(chain @ (Chain::Polygon | Chain::PolygonMumbai)) => {
+                let oracle = Polygon::new(chain).map_err(ProcessorError::FeeOracle)?;
+
+                time_rpc("gas_station", oracle.estimate_eip1559_fees())
+                    .await
+                    .map_err(ProcessorError::FeeOracle)?
+            },
+            _ => {
+                time_rpc(
+                    "eth_feeHistory",
+                    contract.client().estimate_eip1559_fees(None),
+                )
+                .await?
+            },
+        };
+
+        Ok(Some(PolygonFeeHints {
+            gas_limit: gas_limit
+                .map(|gas| scale(gas, self.gas_limit_multiplier_pct))
+                .transpose()?
+                .unwrap_or_default(),
+            max_fee_per_gas: scale(max_fee, self.max_fee_multiplier_pct)?,
+            max_priority_fee_per_gas: scale(priority_fee, self.priority_fee_multiplier_pct)?,
+        }))
+    }
+}
+
+fn scale(value: U256, pct: u64) -> Result<u64, ProcessorError> {
+    let scaled = value.saturating_mul(pct.into()) / 100;
+
+    u64::try_from(scaled).map_err(|_| anyhow!("estimate {scaled} does not fit in 64 bits").into())
+}
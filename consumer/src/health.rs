@@ -0,0 +1,207 @@
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::{PoisonError, RwLock},
+    time::{Duration, Instant},
+};
+
+use holaplex_hub_nfts_polygon_core::{db::Connection, metrics};
+use hub_core::{clap, prelude::*, tokio};
+use poem::{
+    get, handler,
+    http::StatusCode,
+    listener::TcpListener,
+    middleware::AddData,
+    web::{Data, Json},
+    EndpointExt, Route, Server,
+};
+use serde::Serialize;
+
+use crate::chains::Chains;
+
+/// How long a single readiness check may take before it is reported as down
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long a failed poll keeps the consumer reported as down when no message arrives to
+/// show that it recovered, as happens on an idle topic
+const FAILURE_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, clap::Args)]
+pub struct HealthArgs {
+    #[arg(long, env, default_value_t = 3000)]
+    pub health_port: u16,
+}
+
+#[derive(Debug, Default)]
+enum ConsumerState {
+    #[default]
+    Starting,
+    Polling,
+    Failed {
+        error: String,
+        at: Instant,
+    },
+    Stopping,
+}
+
+/// What the consume loop last observed from the Kafka consumer.
+///
+/// hub-core does not expose the partitions assigned to its consumer, so readiness is
+/// judged by whether the loop is polling and its last poll did not fail recently.
+#[derive(Debug, Clone, Default)]
+pub struct ConsumerStatus(Arc<RwLock<ConsumerState>>);
+
+impl ConsumerStatus {
+    /// Records that the consumer is polling for messages without errors
+    pub fn polling(&self) {
+        self.set(ConsumerState::Polling);
+    }
+
+    /// Records that the last poll of the consumer failed
+    pub fn failed(&self, error: &impl std::fmt::Debug) {
+        self.set(ConsumerState::Failed {
+            error: format!("{error:?}"),
+            at: Instant::now(),
+        });
+    }
+
+    /// Records that the consumer stopped polling because the service is shutting down
+    pub fn stopping(&self) {
+        self.set(ConsumerState::Stopping);
+    }
+
+    fn set(&self, state: ConsumerState) {
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = state;
+    }
+
+    fn check(&self) -> Result<(), String> {
+        match &*self.0.read().unwrap_or_else(PoisonError::into_inner) {
+            ConsumerState::Polling => Ok(()),
+            ConsumerState::Failed { at, .. } if at.elapsed() >= FAILURE_WINDOW => Ok(()),
+            ConsumerState::Starting => Err("consumer has not started polling".to_owned()),
+            ConsumerState::Failed { error, .. } => Err(error.clone()),
+            ConsumerState::Stopping => Err("consumer is shutting down".to_owned()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Up,
+    Down,
+}
+
+#[derive(Debug, Serialize)]
+struct Check {
+    status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl From<Result<(), String>> for Check {
+    fn from(result: Result<(), String>) -> Self {
+        match result {
+            Ok(()) => Self {
+                status: Status::Up,
+                error: None,
+            },
+            Err(error) => Self {
+                status: Status::Down,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Readiness {
+    status: Status,
+    checks: BTreeMap<String, Check>,
+}
+
+#[derive(Clone)]
+struct Dependencies {
+    db: Connection,
+    chains: Chains,
+    consumer: ConsumerStatus,
+}
+
+/// Serves `/health`, answering as long as the process runs, `/ready`, which checks the
+/// database, the RPC endpoint of every network and the Kafka consumer, and `/metrics`
+///
+/// # Errors
+/// This function fails if the server cannot bind its port
+pub async fn serve(
+    args: HealthArgs,
+    db: Connection,
+    chains: Chains,
+    consumer: ConsumerStatus,
+) -> Result<()> {
+    let app = Route::new()
+        .at("/health", get(health))
+        .at("/ready", get(ready))
+        .at("/metrics", get(export_metrics))
+        .with(AddData::new(Dependencies {
+            db,
+            chains,
+            consumer,
+        }));
+
+    Server::new(TcpListener::bind(format!("0.0.0.0:{}", args.health_port)))
+        .run(app)
+        .await
+        .map_err(Into::into)
+}
+
+#[handler]
+fn health() -> Json<Check> {
+    Json(Ok(()).into())
+}
+
+#[handler]
+fn export_metrics() -> poem::Result<String> {
+    metrics::encode().map_err(Into::into)
+}
+
+#[handler]
+async fn ready(Data(deps): Data<&Dependencies>) -> (StatusCode, Json<Readiness>) {
+    let mut checks = BTreeMap::new();
+
+    checks.insert(
+        "database".to_owned(),
+        timed(async { deps.db.ping().await.map_err(|e| e.to_string()) })
+            .await
+            .into(),
+    );
+
+    for contracts in deps.chains.networks() {
+        checks.insert(
+            format!("rpc:{}", contracts.network()),
+            timed(async {
+                contracts
+                    .check_chain_id()
+                    .await
+                    .map_err(|e| e.chain().join(": "))
+            })
+            .await
+            .into(),
+        );
+    }
+
+    checks.insert("kafka".to_owned(), deps.consumer.check().into());
+
+    let ready = checks.values().all(|check| check.status == Status::Up);
+    let (code, status) = if ready {
+        (StatusCode::OK, Status::Up)
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, Status::Down)
+    };
+
+    (code, Json(Readiness { status, checks }))
+}
+
+async fn timed(check: impl Future<Output = Result<(), String>>) -> Result<(), String> {
+    tokio::time::timeout(CHECK_TIMEOUT, check)
+        .await
+        .unwrap_or_else(|_| Err(format!("timed out after {CHECK_TIMEOUT:?}")))
+}
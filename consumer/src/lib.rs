@@ -2,7 +2,17 @@
 #![warn(clippy::pedantic, clippy::cargo)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod chains;
+pub mod contracts;
 pub mod events;
+pub mod fees;
+pub mod health;
+pub mod outbox;
+pub mod permits;
+pub mod retry;
+pub mod shutdown;
+pub mod simulation;
+pub mod workers;
 
 use ethers::providers::{Http, Provider};
 use holaplex_hub_nfts_polygon_core::edition_contract;
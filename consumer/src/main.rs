@@ -1,14 +1,28 @@
-use std::sync::Arc;
+use std::time::Duration;
 
-use ethers::{providers::Provider, types::Address};
-use holaplex_hub_nfts_polygon::events::Processor;
+use ethers::types::Address;
+use holaplex_hub_nfts_polygon::{
+    chains::{ChainArgs, ChainConfig, Chains},
+    events::Processor,
+    fees::FeeArgs,
+    health::{self, ConsumerStatus, HealthArgs},
+    outbox::{OutboxArgs, Relay},
+    permits::PermitArgs,
+    retry::RetryArgs,
+    shutdown::{self, ShutdownArgs},
+    simulation::SimulationArgs,
+    workers::WorkerPool,
+};
 use holaplex_hub_nfts_polygon_core::{
     db::{Connection, DbArgs},
-    edition_contract,
     proto::PolygonNftEvents,
-    Services,
+    DeadLetter, Services,
+};
+use hub_core::{
+    clap,
+    prelude::*,
+    tokio::{self, sync::watch, time::Instant},
 };
-use hub_core::{clap, prelude::*, tokio};
 
 #[derive(Debug, clap::Args)]
 #[command(version, author, about)]
@@ -16,11 +30,44 @@ pub struct Args {
     #[command(flatten)]
     pub db: DbArgs,
 
+    #[command(flatten)]
+    pub retry: RetryArgs,
+
+    #[command(flatten)]
+    pub permits: PermitArgs,
+
+    #[command(flatten)]
+    pub chains: ChainArgs,
+
+    #[command(flatten)]
+    pub simulation: SimulationArgs,
+
+    #[command(flatten)]
+    pub fees: FeeArgs,
+
+    #[command(flatten)]
+    pub outbox: OutboxArgs,
+
+    #[command(flatten)]
+    pub shutdown: ShutdownArgs,
+
+    #[command(flatten)]
+    pub health: HealthArgs,
+
     #[arg(long, env)]
     pub polygon_edition_contract: String,
 
     #[arg(long, env)]
     pub polygon_rpc_endpoint: String,
+
+    #[arg(long, env, default_value_t = 16)]
+    pub processor_concurrency: usize,
+
+    #[arg(long, env, default_value_t = 1)]
+    pub processor_queue_size: usize,
+
+    #[arg(long, env, default_value = "hub-nfts-polygon-dead-letters")]
+    pub dead_letter_topic: String,
 }
 
 pub fn main() {
@@ -31,21 +78,46 @@ pub fn main() {
     hub_core::run(opts, |common, args| {
         let Args {
             db,
+            retry,
+            permits,
+            chains,
+            simulation,
+            fees,
+            outbox,
+            shutdown,
+            health,
             polygon_edition_contract,
             polygon_rpc_endpoint,
+            processor_concurrency,
+            processor_queue_size,
+            dead_letter_topic,
         } = args;
 
         common.rt.block_on(async move {
-            let edition_contract_address: Address = polygon_edition_contract.parse()?;
+            let edition_contract: Address = polygon_edition_contract.parse()?;
 
-            let provider = Arc::new(Provider::try_from(polygon_rpc_endpoint)?);
-            let edition_contract = Arc::new(edition_contract::EditionContract::new(
-                edition_contract_address,
-                provider,
-            ));
+            let project_contracts = chains.project_contracts()?;
+            let mut configs = chains.extra_chains()?;
+            configs.push(ChainConfig {
+                network: chains.default_network.clone(),
+                chain_id: None,
+                rpc_url: polygon_rpc_endpoint,
+                edition_contract,
+                deployer: None,
+            });
+            let chains = Chains::connect(
+                chains.default_network,
+                configs,
+                &permits,
+                Duration::from_secs(chains.contract_owner_ttl_secs),
+            )
+            .await?;
             let connection = Connection::new(db)
                 .await
                 .context("failed to get database connection")?;
+            chains
+                .register_contracts(&connection, project_contracts)
+                .await?;
 
             let cons = common.consumer_cfg.build::<Services>().await?;
             let producer = common
@@ -53,25 +125,101 @@ pub fn main() {
                 .clone()
                 .build::<PolygonNftEvents>()
                 .await?;
-            let event_processor = Processor::new(connection, producer, edition_contract);
+            let mut dead_letter_cfg = common.producer_cfg.clone();
+            dead_letter_cfg.topic = dead_letter_topic;
+            let dead_letters = dead_letter_cfg.build::<DeadLetter>().await?;
+
+            let relay = Relay::new(connection.clone(), producer, outbox);
+            let (stop_relay, relay_stopped) = watch::channel(false);
+            let relay_task = tokio::spawn(relay.clone().run(relay_stopped));
+
+            let consumer_status = ConsumerStatus::default();
+            tokio::spawn({
+                let server = health::serve(
+                    health,
+                    connection.clone(),
+                    chains.clone(),
+                    consumer_status.clone(),
+                );
+
+                async move {
+                    if let Err(e) = server.await {
+                        error!("health server failed: {e:?}");
+                    }
+                }
+            });
+
+            let event_processor =
+                Processor::new(connection, relay, chains, permits, retry, simulation, fees);
+            let workers = WorkerPool::new(
+                &event_processor,
+                &dead_letters,
+                processor_concurrency,
+                processor_queue_size,
+            );
+
+            let signal = shutdown::signal();
+            tokio::pin!(signal);
 
             let mut stream = cons.stream();
+            let mut result = Ok(());
+            consumer_status.polling();
             loop {
-                let event_processor = event_processor.clone();
+                let msg = tokio::select! {
+                    () = &mut signal => break,
+                    msg = stream.next() => msg,
+                };
 
-                match stream.next().await {
+                match msg {
                     Some(Ok(msg)) => {
                         info!(?msg, "message received");
+                        consumer_status.polling();
+
+                        // A saturated worker may wait on a message in retry backoff, so the
+                        // signal is raced against queueing as well
+                        tokio::select! {
+                            () = &mut signal => {
+                                warn!("shutdown requested before the message could be queued");
 
-                        tokio::spawn(async move { event_processor.process(msg).await });
-                        tokio::task::yield_now().await;
+                                break;
+                            },
+                            res = workers.dispatch(msg) => {
+                                if let Err(e) = res {
+                                    error!("failed to dispatch message: {e:?}");
+                                    result = Err(e);
+
+                                    break;
+                                }
+                            },
+                        }
                     },
                     None => (),
                     Some(Err(e)) => {
                         warn!("failed to get message {:?}", e);
+                        consumer_status.failed(&e);
                     },
                 }
             }
+
+            // hub-core's consumer does not expose offset commits, so a consumed message
+            // that is dropped may not be redelivered. Every queued message is therefore
+            // finished, even past the deadline, and the worker queues are kept small so
+            // that fits in the grace period. Relaying only gets the time left over since
+            // the outbox is persisted and picked up again on the next start.
+            info!("shutting down, no longer consuming messages");
+            consumer_status.stopping();
+            drop(stream);
+            let deadline = Instant::now() + shutdown.timeout();
+            workers.shutdown(deadline).await;
+
+            stop_relay.send_replace(true);
+            if tokio::time::timeout_at(deadline, relay_task).await.is_err() {
+                warn!("outbox was not drained before the deadline");
+            }
+
+            info!("shutdown complete");
+
+            result
         })
     });
 }
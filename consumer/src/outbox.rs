@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use holaplex_hub_nfts_polygon_core::{
+    db::Connection,
+    proto::{PolygonNftEventKey, PolygonNftEvents},
+    sea_orm::TransactionTrait,
+    Outbox,
+};
+use hub_core::{
+    clap,
+    prelude::*,
+    producer::Producer,
+    tokio::{
+        self,
+        sync::{watch, Notify},
+    },
+};
+use prost::Message;
+
+/// Arguments controlling how queued events are relayed to Kafka
+#[derive(Debug, Clone, Copy, clap::Args)]
+pub struct OutboxArgs {
+    #[arg(long, env, default_value_t = 100)]
+    pub outbox_batch_size: u64,
+    #[arg(long, env, default_value_t = 1000)]
+    pub outbox_poll_interval_ms: u64,
+}
+
+/// Publishes events queued in the outbox table and deletes them once sent.
+///
+/// Only one consumer replica relays at a time, holding an advisory lock, so events are
+/// published in the order they were queued. A row is only deleted once Kafka acknowledged
+/// it, so a crash between the two results in a duplicate rather than a lost event.
+#[derive(Clone)]
+pub struct Relay {
+    db: Connection,
+    producer: Producer<PolygonNftEvents>,
+    notify: Arc<Notify>,
+    args: OutboxArgs,
+}
+
+impl Relay {
+    #[must_use]
+    pub fn new(db: Connection, producer: Producer<PolygonNftEvents>, args: OutboxArgs) -> Self {
+        Self {
+            db,
+            producer,
+            notify: Arc::default(),
+            args,
+        }
+    }
+
+    /// Wakes the relay after new events were committed to the outbox
+    pub fn notify(&self) {
+        self.notify.notify_one();
+    }
+
+    /// Relays queued events, polling in case a wakeup was missed, until `stop` is set.
+    /// Events still queued at that point are relayed before returning.
+    pub async fn run(self, mut stop: watch::Receiver<bool>) {
+        let interval = Duration::from_millis(self.args.outbox_poll_interval_ms);
+
+        while !*stop.borrow() {
+            match self.relay_batch().await {
+                Ok(sent) if sent == self.args.outbox_batch_size => continue,
+                Ok(_) => (),
+                Err(e) => warn!("failed to relay outbox: {e:?}"),
+            }
+
+            tokio::select! {
+                () = self.notify.notified() => (),
+                () = tokio::time::sleep(interval) => (),
+                _ = stop.changed() => (),
+            }
+        }
+
+        self.drain().await;
+    }
+
+    /// Relays batches until the outbox is empty or publishing fails
+    async fn drain(&self) {
+        loop {
+            match self.relay_batch().await {
+                Ok(sent) if sent == self.args.outbox_batch_size => (),
+                Ok(_) => break,
+                Err(e) => {
+                    warn!("failed to drain outbox: {e:?}");
+
+                    break;
+                },
+            }
+        }
+    }
+
+    /// Publishes a batch of queued events, returning how many rows were handled. Returns
+    /// zero without publishing if another replica is relaying.
+    async fn relay_batch(&self) -> Result<u64> {
+        let txn = self.db.get().begin().await?;
+
+        if !Outbox::try_lock_relay(&txn).await? {
+            return Ok(0);
+        }
+
+        let rows = Outbox::find_pending(&txn, self.args.outbox_batch_size).await?;
+        let mut sent = Vec::with_capacity(rows.len());
+        let mut failed = Vec::new();
+
+        for row in rows {
+            let decoded = PolygonNftEventKey::decode(row.key.as_slice()).and_then(|key| {
+                PolygonNftEvents::decode(row.payload.as_slice()).map(|event| (key, event))
+            });
+
+            let (key, event) = match decoded {
+                Ok(decoded) => decoded,
+                Err(e) => {
+                    error!(
+                        id = %row.id,
+                        created_at = %row.created_at,
+                        "failed to decode outbox event: {e:?}"
+                    );
+
+                    failed.push(row.id);
+                    continue;
+                },
+            };
+
+            if let Err(e) = self.producer.send(Some(&event), Some(&key)).await {
+                warn!(id = %row.id, "failed to publish outbox event: {e:?}");
+
+                break;
+            }
+
+            sent.push(row.id);
+        }
+
+        let count = (sent.len() + failed.len()).try_into()?;
+
+        if !sent.is_empty() {
+            Outbox::delete_sent(&txn, sent).await?;
+        }
+
+        if !failed.is_empty() {
+            Outbox::mark_failed(&txn, failed).await?;
+        }
+
+        txn.commit().await?;
+
+        Ok(count)
+    }
+}
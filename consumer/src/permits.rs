@@ -0,0 +1,26 @@
+use hub_core::{chrono::NaiveDateTime, clap};
+
+/// Arguments for signing and submitting edition transfer permits
+#[derive(Debug, Clone, clap::Args)]
+pub struct PermitArgs {
+    #[arg(long, env, default_value = "EditionContract")]
+    pub polygon_edition_contract_name: String,
+
+    #[arg(long, env, default_value = "1")]
+    pub polygon_edition_contract_version: String,
+
+    #[arg(long, env, default_value_t = false)]
+    pub verify_permit_hashes: bool,
+
+    #[arg(long, env, default_value_t = 3600)]
+    pub permit_deadline_secs: u64,
+}
+
+impl PermitArgs {
+    /// The unix timestamp after which a permit signed at `now` is no longer accepted
+    #[must_use]
+    pub fn deadline(&self, now: NaiveDateTime) -> i64 {
+        now.timestamp()
+            .saturating_add(i64::try_from(self.permit_deadline_secs).unwrap_or(i64::MAX))
+    }
+}
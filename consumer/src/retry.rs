@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use hub_core::clap;
+use rand::Rng;
+
+/// Arguments controlling how transient processing failures are retried
+#[derive(Debug, Clone, Copy, clap::Args)]
+pub struct RetryArgs {
+    #[arg(long, env, default_value_t = 5)]
+    pub processor_max_attempts: u32,
+    #[arg(long, env, default_value_t = 250)]
+    pub processor_backoff_base_ms: u64,
+    #[arg(long, env, default_value_t = 30_000)]
+    pub processor_backoff_max_ms: u64,
+}
+
+impl RetryArgs {
+    /// The delay before the attempt following `attempt`, doubling per attempt up to the
+    /// configured maximum with the upper half randomized so replicas don't retry in lockstep
+    #[must_use]
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .processor_backoff_base_ms
+            .saturating_mul(2_u64.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.processor_backoff_max_ms);
+        let half = exponential / 2;
+
+        Duration::from_millis(half + rand::thread_rng().gen_range(0..=half))
+    }
+}
@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use hub_core::{clap, prelude::*, tokio};
+
+/// Arguments controlling how long in-flight work may take to finish on shutdown
+#[derive(Debug, Clone, Copy, clap::Args)]
+pub struct ShutdownArgs {
+    /// Kept below the 30 second termination grace period of Kubernetes so the process
+    /// exits on its own before it is killed
+    #[arg(long, env, default_value_t = 25)]
+    pub shutdown_timeout_secs: u64,
+}
+
+impl ShutdownArgs {
+    #[must_use]
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.shutdown_timeout_secs)
+    }
+}
+
+/// Resolves once the process is asked to stop, by SIGTERM from the orchestrator or by
+/// Ctrl-C when running locally
+pub async fn signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!("failed to listen for ctrl-c: {e:?}");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            },
+            Err(e) => {
+                error!("failed to listen for SIGTERM: {e:?}");
+                std::future::pending::<()>().await;
+            },
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => info!("received ctrl-c"),
+        () = terminate => info!("received SIGTERM"),
+    }
+}
@@ -0,0 +1,60 @@
+use ethers::{
+    providers::{JsonRpcError, Middleware, ProviderError, RpcError},
+    types::{transaction::eip2718::TypedTransaction, Address},
+};
+use holaplex_hub_nfts_polygon_core::{metrics::time_rpc, ProcessorError};
+use hub_core::clap;
+
+use crate::EditionContract;
+
+/// Arguments controlling the pre-flight check of built transactions
+#[derive(Debug, Clone, Copy, clap::Args)]
+pub struct SimulationArgs {
+    #[arg(long, env, default_value_t = false)]
+    pub simulate_transactions: bool,
+}
+
+impl SimulationArgs {
+    /// Runs `tx` with `eth_call` as if `from` submitted it, so transactions that would revert
+    /// on-chain are reported as failures instead of being handed to the treasury. Does nothing
+    /// unless simulation is enabled.
+    ///
+    /// # Errors
+    /// This function fails with [`ProcessorError::Reverted`] if the call reverts, or if the
+    /// node cannot be reached
+    pub async fn check(
+        &self,
+        contract: &EditionContract,
+        from: Address,
+        tx: &TypedTransaction,
+    ) -> Result<(), ProcessorError> {
+        if !self.simulate_transactions {
+            return Ok(());
+        }
+
+        let mut tx = tx.clone();
+        tx.set_from(from);
+
+        time_rpc("eth_call", contract.client().call(&tx, None))
+            .await
+            .map(|_| ())
+            .map_err(call_error)
+    }
+}
+
+/// Reports reverted calls as [`ProcessorError::Reverted`] and anything else as an RPC
+/// failure
+pub(crate) fn call_error(e: ProviderError) -> ProcessorError {
+    match e.as_error_response() {
+        Some(response) if response.is_revert() => ProcessorError::Reverted(revert_reason(response)),
+        _ => e.into(),
+    }
+}
+
+/// The `Error(string)` message of a revert, or the raw revert data for custom errors
+fn revert_reason(response: &JsonRpcError) -> String {
+    response
+        .decode_revert_data::<String>()
+        .or_else(|| response.as_revert_data().map(|data| data.to_string()))
+        .unwrap_or_else(|| response.message.clone())
+}
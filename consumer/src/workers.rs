@@ -0,0 +1,198 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use holaplex_hub_nfts_polygon_core::{
+    metrics::{self, EVENTS_FAILED, EVENTS_PROCESSED, EVENT_DURATION},
+    proto::{nft_events::Event as NftEvents, MintEditionBatchTransaction, MintEditionTransaction},
+    DeadLetter, Services,
+};
+use hub_core::{
+    prelude::*,
+    producer::Producer,
+    tokio::{self, sync::mpsc, task::JoinHandle, time::Instant},
+    tracing::{info_span, Instrument},
+};
+
+use crate::events::{event_key, event_name, Failure, Processor};
+
+/// A fixed set of workers that process consumer messages concurrently.
+///
+/// Every message is routed to a worker by its ordering key, so messages for the same
+/// collection are processed one after another in the order they were received while
+/// unrelated collections are handled in parallel.
+pub struct WorkerPool {
+    senders: Vec<mpsc::Sender<Services>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl WorkerPool {
+    /// Spawns `concurrency` workers, each buffering up to `queue_size` messages.
+    /// Messages that fail processing are published to `dead_letters`.
+    #[must_use]
+    pub fn new(
+        processor: &Processor,
+        dead_letters: &Producer<DeadLetter>,
+        concurrency: usize,
+        queue_size: usize,
+    ) -> Self {
+        let (senders, workers) = (0..concurrency.max(1))
+            .map(|worker| {
+                let (tx, rx) = mpsc::channel(queue_size.max(1));
+                let handle = tokio::spawn(run_worker(
+                    worker,
+                    processor.clone(),
+                    dead_letters.clone(),
+                    rx,
+                ));
+
+                (tx, handle)
+            })
+            .unzip();
+
+        Self { senders, workers }
+    }
+
+    /// Queues a message on the worker that owns its ordering key, waiting for room if
+    /// that worker is saturated
+    ///
+    /// # Errors
+    /// This function fails if the worker has stopped
+    pub async fn dispatch(&self, msg: Services) -> Result<()> {
+        let mut hasher = DefaultHasher::new();
+        ordering_key(&msg).hash(&mut hasher);
+        let worker = usize::try_from(hasher.finish() % self.senders.len() as u64)?;
+
+        self.senders[worker]
+            .send(msg)
+            .await
+            .map_err(|_| anyhow!("worker {worker} has stopped"))
+    }
+
+    /// Stops accepting messages and waits for the workers to finish every message already
+    /// queued on them.
+    ///
+    /// Workers are never aborted since a consumed message that is dropped may not be
+    /// redelivered. Passing `deadline` only logs a warning, so the queues must be kept
+    /// small enough to be drained within the termination grace period.
+    pub async fn shutdown(self, deadline: Instant) {
+        let Self { senders, workers } = self;
+        drop(senders);
+
+        let joined = async {
+            for (worker, handle) in workers.into_iter().enumerate() {
+                if let Err(e) = handle.await {
+                    error!(worker, "worker panicked: {e:?}");
+                }
+            }
+        };
+        tokio::pin!(joined);
+
+        if tokio::time::timeout_at(deadline, &mut joined)
+            .await
+            .is_err()
+        {
+            warn!("shutdown deadline reached, still waiting for workers to drain");
+
+            joined.await;
+        }
+
+        info!("all workers drained");
+    }
+}
+
+async fn run_worker(
+    worker: usize,
+    processor: Processor,
+    dead_letters: Producer<DeadLetter>,
+    mut rx: mpsc::Receiver<Services>,
+) {
+    while let Some(msg) = rx.recv().await {
+        let key = event_key(&msg);
+        let span = info_span!(
+            "process_message",
+            worker,
+            topic = msg.topic(),
+            event = event_name(&msg),
+            key_id = %key.id,
+            project_id = %key.project_id,
+            user_id = %key.user_id,
+        );
+
+        handle(&processor, &dead_letters, msg)
+            .instrument(span)
+            .await;
+    }
+}
+
+async fn handle(processor: &Processor, dead_letters: &Producer<DeadLetter>, msg: Services) {
+    let event = event_name(&msg);
+    let started = Instant::now();
+
+    let result = processor.process(msg.clone()).await;
+    if let Some(event) = event {
+        EVENT_DURATION
+            .with_label_values(&[event, metrics::outcome(&result)])
+            .observe(started.elapsed().as_secs_f64());
+    }
+
+    match result {
+        Ok(()) => {
+            if let Some(event) = event {
+                EVENTS_PROCESSED.with_label_values(&[event]).inc();
+            }
+
+            info!(elapsed = ?started.elapsed(), "message processed");
+        },
+        Err(Failure { attempts, error }) => {
+            if let Some(event) = event {
+                EVENTS_FAILED
+                    .with_label_values(&[event, error.reason().as_str_name()])
+                    .inc();
+            }
+
+            error!(
+                attempts,
+                elapsed = ?started.elapsed(),
+                "failed to process message: {:?}",
+                error.chain()
+            );
+
+            let (key, dead_letter) = DeadLetter::new(&msg, &error, attempts);
+
+            if let Err(e) = dead_letters.send(Some(&dead_letter), Some(&key)).await {
+                error!("failed to publish dead letter: {e:?}");
+            }
+        },
+    }
+}
+
+/// The key that determines which messages must be processed in order.
+///
+/// Drop and mint events share their collection id so a mint is never handled before the
+/// drop it belongs to has been created or updated. A batch is ordered with its collection
+/// when all of its mints belong to one, as airdrops do. A batch spanning several
+/// collections keeps its own key and is not ordered against their drop events.
+fn ordering_key(msg: &Services) -> &str {
+    match msg {
+        Services::Nfts(key, e) => match &e.event {
+            Some(
+                NftEvents::PolygonMintDrop(MintEditionTransaction { collection_id, .. })
+                | NftEvents::PolygonRetryMintDrop(MintEditionTransaction { collection_id, .. }),
+            ) => collection_id,
+            Some(NftEvents::PolygonMintDropBatch(MintEditionBatchTransaction { mints })) => {
+                match mints.split_first() {
+                    Some((first, rest))
+                        if rest.iter().all(|m| m.collection_id == first.collection_id) =>
+                    {
+                        &first.collection_id
+                    },
+                    _ => &key.id,
+                }
+            },
+            Some(_) | None => &key.id,
+        },
+        Services::Treasuries(key, _) => &key.id,
+    }
+}
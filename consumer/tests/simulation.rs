@@ -0,0 +1,73 @@
+//! Runs the pre-flight simulation against a local anvil node. The test is skipped when
+//! anvil is not on the path; CI installs it with foundry.
+
+use std::process::Command;
+
+use ethers::{
+    middleware::SignerMiddleware,
+    providers::{Http, Middleware, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address, Bytes, TransactionRequest},
+    utils::Anvil,
+};
+use holaplex_hub_nfts_polygon::{simulation::SimulationArgs, EditionContract};
+use holaplex_hub_nfts_polygon_core::{edition_contract, ProcessorError};
+use hub_core::prelude::*;
+
+/// Creation code of a stub that answers every call made by the first anvil account and
+/// reverts any other caller with the ERC-1155 transfer authorization error, standing in for
+/// a mint the edition contract refuses
+const STUB_INIT_CODE: &str = "0x6097600c60003960976000f33373f39fd6e51aad88f6f4ce6ab8827279cfffb92266146095577f08c379a0000000000000000000000000000000000000000000000000000000006000526020600452602e6024527f455243313135353a2063616c6c6572206973206e6f7420746f6b656e206f776e6044527f6572206f7220617070726f76656400000000000000000000000000000000000060645260846000fd5b00";
+const STUB_OWNER: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+/// Whether the anvil binary can be run, as foundry is not part of the usual toolchain
+fn anvil_installed() -> bool {
+    Command::new("anvil").arg("--version").output().is_ok()
+}
+
+#[tokio::test]
+async fn check_reports_reverted_mints_with_their_reason() {
+    if !anvil_installed() {
+        eprintln!("skipping: anvil is not installed");
+
+        return;
+    }
+
+    let anvil = Anvil::new().spawn();
+    let provider = Provider::<Http>::try_from(anvil.endpoint()).unwrap();
+    let wallet = LocalWallet::from(anvil.keys()[0].clone()).with_chain_id(anvil.chain_id());
+    let owner = wallet.address();
+    assert_eq!(owner, STUB_OWNER.parse::<Address>().unwrap());
+
+    let client = SignerMiddleware::new(provider.clone(), wallet);
+    let deploy = TransactionRequest::new().data(STUB_INIT_CODE.parse::<Bytes>().unwrap());
+    let address = client
+        .send_transaction(deploy, None)
+        .await
+        .unwrap()
+        .await
+        .unwrap()
+        .and_then(|receipt| receipt.contract_address)
+        .unwrap();
+
+    let contract: EditionContract = Arc::new(edition_contract::EditionContract::new(
+        address,
+        Arc::new(provider),
+    ));
+    let receiver = anvil.addresses()[1];
+    let mint = contract
+        .safe_transfer_from(owner, receiver, 1.into(), 1.into(), Bytes::new())
+        .tx;
+    let simulation = SimulationArgs {
+        simulate_transactions: true,
+    };
+
+    simulation.check(&contract, owner, &mint).await.unwrap();
+
+    match simulation.check(&contract, receiver, &mint).await {
+        Err(ProcessorError::Reverted(reason)) => {
+            assert_eq!(reason, "ERC1155: caller is not token owner or approved");
+        },
+        other => panic!("expected a revert, got {other:?}"),
+    }
+}
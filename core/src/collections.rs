@@ -2,9 +2,7 @@ use holaplex_hub_nfts_polygon_entity::{
     collections::{ActiveModel, Column, Entity, Model, Relation},
     mints,
 };
-use sea_orm::{prelude::*, JoinType, QuerySelect};
-
-use crate::db::Connection;
+use sea_orm::{prelude::*, ConnectionTrait, JoinType, QuerySelect, Statement};
 
 pub struct Collection;
 
@@ -13,9 +11,7 @@ impl Collection {
     ///
     /// # Errors
     /// This function fails if ...
-    pub async fn create(db: &Connection, model: Model) -> Result<Model, DbErr> {
-        let conn = db.get();
-
+    pub async fn create<C: ConnectionTrait>(conn: &C, model: Model) -> Result<Model, DbErr> {
         let active_model: ActiveModel = model.into();
 
         active_model.insert(conn).await
@@ -25,8 +21,7 @@ impl Collection {
     ///
     /// # Errors
     /// This function fails if ...
-    pub async fn update(db: &Connection, am: ActiveModel) -> Result<Model, DbErr> {
-        let conn = db.get();
+    pub async fn update<C: ConnectionTrait>(conn: &C, am: ActiveModel) -> Result<Model, DbErr> {
         am.update(conn).await
     }
 
@@ -39,36 +34,39 @@ impl Collection {
     ///
     /// # Errors
     /// This function fails if ...
-    pub async fn find_by_id(db: &Connection, id: Uuid) -> Result<Option<Model>, DbErr> {
-        let conn = db.get();
-
+    pub async fn find_by_id<C: ConnectionTrait>(
+        conn: &C,
+        id: Uuid,
+    ) -> Result<Option<Model>, DbErr> {
         Entity::find().filter(Column::Id.eq(id)).one(conn).await
     }
 
-    /// Res
+    /// Reserves the next edition id from the `collections_edition_id_seq` sequence.
+    /// Sequence values are never handed out twice, so concurrent drop creations across
+    /// consumer instances always receive distinct edition ids.
     ///
     /// # Errors
-    /// This function fails if ...
-    pub async fn find_max_edition_id(db: &Connection) -> Result<Option<i32>, DbErr> {
-        let conn = db.get();
+    /// This function fails if the sequence cannot be advanced
+    pub async fn next_edition_id<C: ConnectionTrait>(conn: &C) -> Result<i32, DbErr> {
+        let row = conn
+            .query_one(Statement::from_string(
+                conn.get_database_backend(),
+                "select nextval('collections_edition_id_seq')::integer as edition_id".to_string(),
+            ))
+            .await?
+            .ok_or_else(|| DbErr::Custom("failed to reserve edition id".to_owned()))?;
 
-        let v: Option<Option<i32>> = Entity::find()
-            .select_only()
-            .column_as(Column::EditionId.max(), QueryAs::EditionId)
-            .into_values::<_, QueryAs>()
-            .one(conn)
-            .await?;
-
-        Ok(v.flatten())
+        row.try_get("", "edition_id")
     }
 
     /// Res
     ///
     /// # Errors
     /// This function fails if ...
-    pub async fn find_by_mint_id(db: &Connection, mint_id: Uuid) -> Result<Option<Model>, DbErr> {
-        let conn = db.get();
-
+    pub async fn find_by_mint_id<C: ConnectionTrait>(
+        conn: &C,
+        mint_id: Uuid,
+    ) -> Result<Option<Model>, DbErr> {
         Entity::find()
             .join(JoinType::InnerJoin, Relation::Mints.def())
             .filter(mints::Column::Id.eq(mint_id))
@@ -76,8 +74,3 @@ impl Collection {
             .await
     }
 }
-
-#[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
-enum QueryAs {
-    EditionId,
-}
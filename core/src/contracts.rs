@@ -0,0 +1,68 @@
+use holaplex_hub_nfts_polygon_entity::contracts::{ActiveModel, Column, Entity, Model};
+use sea_orm::{
+    prelude::*,
+    sea_query::{Expr, OnConflict},
+    Set, TransactionTrait,
+};
+
+use crate::db::Connection;
+
+pub struct Contract;
+
+impl Contract {
+    /// Makes `address` the contract new drops of `project_id` are created on in `network`,
+    /// releasing any contract the project was registered with before
+    ///
+    /// # Errors
+    /// This function fails if the transaction cannot be committed
+    pub async fn register(
+        db: &Connection,
+        network: &str,
+        address: &str,
+        project_id: Uuid,
+    ) -> Result<(), DbErr> {
+        let txn = db.get().begin().await?;
+
+        Entity::update_many()
+            .col_expr(Column::ProjectId, Expr::value(Option::<Uuid>::None))
+            .filter(Column::Network.eq(network))
+            .filter(Column::ProjectId.eq(project_id))
+            .filter(Column::Address.ne(address))
+            .exec(&txn)
+            .await?;
+
+        Entity::insert(ActiveModel {
+            network: Set(network.to_owned()),
+            address: Set(address.to_owned()),
+            project_id: Set(Some(project_id)),
+            ..Default::default()
+        })
+        .on_conflict(
+            OnConflict::columns([Column::Network, Column::Address])
+                .update_column(Column::ProjectId)
+                .to_owned(),
+        )
+        .exec(&txn)
+        .await?;
+
+        txn.commit().await
+    }
+
+    /// Res
+    ///
+    /// # Errors
+    /// This function fails if ...
+    pub async fn find_by_project_id(
+        db: &Connection,
+        network: &str,
+        project_id: Uuid,
+    ) -> Result<Option<Model>, DbErr> {
+        let conn = db.get();
+
+        Entity::find()
+            .filter(Column::Network.eq(network))
+            .filter(Column::ProjectId.eq(project_id))
+            .one(conn)
+            .await
+    }
+}
@@ -2,6 +2,7 @@ use std::time::Duration;
 
 use hub_core::{anyhow::Result, clap, prelude::*};
 pub use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use sea_orm::{ConnectionTrait, DbErr, Statement};
 
 /// Arguments for establishing a database connection
 #[derive(Debug, clap::Args)]
@@ -54,4 +55,18 @@ impl Connection {
     pub fn get(&self) -> &DatabaseConnection {
         &self.0
     }
+
+    /// Res
+    ///
+    /// # Errors
+    /// This function fails if ...
+    pub async fn ping(&self) -> Result<(), DbErr> {
+        self.0
+            .execute(Statement::from_string(
+                self.0.get_database_backend(),
+                "SELECT 1".to_string(),
+            ))
+            .await
+            .map(|_| ())
+    }
 }
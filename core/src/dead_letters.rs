@@ -0,0 +1,61 @@
+use std::time::SystemTime;
+
+use prost::Message;
+use prost_types::Timestamp;
+
+use crate::{ProcessorError, Services};
+
+/// A consumer message that could not be processed, published to the dead-letter topic so
+/// operators can inspect it and re-drive it later
+#[derive(Clone, PartialEq, Message)]
+pub struct DeadLetter {
+    /// The topic the original message was consumed from
+    #[prost(string, tag = "1")]
+    pub topic: String,
+    /// The protobuf encoded key of the original message
+    #[prost(bytes = "vec", tag = "2")]
+    pub key: Vec<u8>,
+    /// The protobuf encoded payload of the original message
+    #[prost(bytes = "vec", tag = "3")]
+    pub payload: Vec<u8>,
+    /// The error chain of the final failure, outermost error first
+    #[prost(string, repeated, tag = "4")]
+    pub errors: Vec<String>,
+    #[prost(uint32, tag = "5")]
+    pub attempts: u32,
+    #[prost(message, optional, tag = "6")]
+    pub failed_at: Option<Timestamp>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct DeadLetterKey {
+    #[prost(string, tag = "1")]
+    pub topic: String,
+    #[prost(string, tag = "2")]
+    pub id: String,
+}
+
+impl DeadLetter {
+    #[must_use]
+    pub fn new(msg: &Services, error: &ProcessorError, attempts: u32) -> (DeadLetterKey, Self) {
+        let (key, payload) = msg.encode();
+
+        let dead_letter_key = DeadLetterKey {
+            topic: msg.topic().to_owned(),
+            id: msg.key_id().to_owned(),
+        };
+
+        (dead_letter_key, Self {
+            topic: msg.topic().to_owned(),
+            key,
+            payload,
+            errors: error.chain(),
+            attempts,
+            failed_at: Some(SystemTime::now().into()),
+        })
+    }
+}
+
+impl hub_core::producer::Message for DeadLetter {
+    type Key = DeadLetterKey;
+}
@@ -0,0 +1,188 @@
+use std::error::Error as StdError;
+
+use ethers::{
+    contract::ContractError,
+    middleware::gas_oracle::GasOracleError,
+    providers::{Http, Provider, ProviderError, RpcError},
+    types::SignatureError,
+};
+use sea_orm::{sqlx, DbErr, RuntimeErr};
+
+use crate::{proto::PolygonFailureReason, validation::ValidationErrors};
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// Errors raised while processing consumer events.
+///
+/// Variants are split by whether the same message can succeed if it is processed again:
+/// RPC transport, database connectivity and Kafka failures are transient, everything else
+/// is permanent and retrying would only fail the same way.
+#[derive(Debug, thiserror::Error)]
+pub enum ProcessorError {
+    #[error("missing {0} in event payload")]
+    MissingField(&'static str),
+    #[error("invalid {field} in event payload")]
+    InvalidField {
+        field: &'static str,
+        #[source]
+        source: BoxError,
+    },
+    #[error("invalid event payload: {0}")]
+    Validation(#[from] ValidationErrors),
+    #[error("{0} not found")]
+    NotFound(&'static str),
+    #[error("network {0} is not configured")]
+    UnknownNetwork(String),
+    #[error("permit expired at {0}")]
+    PermitExpired(i64),
+    #[error("permit {0} does not match the pending transfer")]
+    PermitMismatch(&'static str),
+    #[error("permit was already submitted")]
+    PermitReplayed,
+    #[error("edition supply is exhausted")]
+    SupplyExhausted,
+    #[error("mint was already burned")]
+    AlreadyBurned,
+    #[error("address does not own the mint")]
+    NotOwner,
+    #[error("invalid permit signature")]
+    InvalidSignature(#[source] SignatureError),
+    #[error("rpc request failed")]
+    Rpc(#[source] ProviderError),
+    #[error("rpc request was rejected by the node")]
+    RpcRejected(#[source] ProviderError),
+    #[error("fee oracle request failed")]
+    FeeOracle(#[source] GasOracleError),
+    #[error("contract call reverted: {0}")]
+    Reverted(String),
+    #[error("contract call failed")]
+    Contract(#[source] ContractError<Provider<Http>>),
+    #[error("database request failed")]
+    Database(#[from] DbErr),
+    #[error("failed to send event")]
+    Kafka(#[source] BoxError),
+    #[error(transparent)]
+    Other(#[from] hub_core::anyhow::Error),
+}
+
+impl ProcessorError {
+    /// Builds an [`InvalidField`](Self::InvalidField) error for `field`, for use with
+    /// [`Result::map_err`]
+    pub fn invalid<E: Into<BoxError>>(field: &'static str) -> impl FnOnce(E) -> Self {
+        move |e| Self::InvalidField {
+            field,
+            source: e.into(),
+        }
+    }
+
+    /// Whether processing the same message again may succeed
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Rpc(_) | Self::FeeOracle(_) | Self::Kafka(_) => true,
+            Self::Database(e) => is_transient(e),
+            Self::MissingField(_)
+            | Self::InvalidField { .. }
+            | Self::Validation(_)
+            | Self::NotFound(_)
+            | Self::UnknownNetwork(_)
+            | Self::PermitExpired(_)
+            | Self::PermitMismatch(_)
+            | Self::PermitReplayed
+            | Self::SupplyExhausted
+            | Self::AlreadyBurned
+            | Self::NotOwner
+            | Self::InvalidSignature(_)
+            | Self::RpcRejected(_)
+            | Self::Reverted(_)
+            | Self::Contract(_)
+            | Self::Other(_) => false,
+        }
+    }
+
+    /// The reason code reported to hub-nfts when the message is given up on
+    #[must_use]
+    pub fn reason(&self) -> PolygonFailureReason {
+        match self {
+            Self::MissingField(_) | Self::InvalidField { .. } | Self::Validation(_) => {
+                PolygonFailureReason::InvalidPayload
+            },
+            Self::NotFound(_) | Self::UnknownNetwork(_) => PolygonFailureReason::NotFound,
+            Self::SupplyExhausted => PolygonFailureReason::SupplyExhausted,
+            Self::Rpc(_) | Self::FeeOracle(_) | Self::Contract(_) => PolygonFailureReason::Rpc,
+            Self::PermitExpired(_)
+            | Self::PermitMismatch(_)
+            | Self::PermitReplayed
+            | Self::AlreadyBurned
+            | Self::NotOwner
+            | Self::InvalidSignature(_)
+            | Self::RpcRejected(_)
+            | Self::Reverted(_) => PolygonFailureReason::Rejected,
+            Self::Database(_) => PolygonFailureReason::Database,
+            Self::Kafka(_) | Self::Other(_) => PolygonFailureReason::Unknown,
+        }
+    }
+
+    /// The error followed by each of its sources, outermost first
+    #[must_use]
+    pub fn chain(&self) -> Vec<String> {
+        std::iter::successors(Some(self as &(dyn StdError + 'static)), |e| e.source())
+            .map(ToString::to_string)
+            .collect()
+    }
+}
+
+/// Whether a database error may not recur: the connection was lost or could not be
+/// acquired, or the transaction lost a serialization conflict or a deadlock
+fn is_transient(e: &DbErr) -> bool {
+    let runtime = match e {
+        DbErr::ConnectionAcquire | DbErr::Conn(_) => return true,
+        DbErr::Exec(e) | DbErr::Query(e) => e,
+        _ => return false,
+    };
+
+    match runtime {
+        RuntimeErr::SqlxError(
+            sqlx::Error::Io(_)
+            | sqlx::Error::PoolTimedOut
+            | sqlx::Error::PoolClosed
+            | sqlx::Error::WorkerCrashed,
+        ) => true,
+        RuntimeErr::SqlxError(sqlx::Error::Database(e)) => {
+            // serialization_failure and deadlock_detected
+            matches!(e.code().as_deref(), Some("40001" | "40P01"))
+        },
+        RuntimeErr::SqlxError(_) | RuntimeErr::Internal(_) => false,
+    }
+}
+
+/// Whether a provider error happened while reaching the node, as opposed to the node
+/// answering with a JSON-RPC error, such as invalid params or insufficient funds, that a
+/// repeated request would receive again
+fn is_transport(e: &ProviderError) -> bool {
+    match e {
+        ProviderError::JsonRpcClientError(e) => e.as_error_response().is_none(),
+        ProviderError::HTTPError(_) => true,
+        _ => false,
+    }
+}
+
+impl From<ProviderError> for ProcessorError {
+    fn from(e: ProviderError) -> Self {
+        if is_transport(&e) {
+            Self::Rpc(e)
+        } else {
+            Self::RpcRejected(e)
+        }
+    }
+}
+
+impl From<ContractError<Provider<Http>>> for ProcessorError {
+    fn from(e: ContractError<Provider<Http>>) -> Self {
+        match e {
+            ContractError::MiddlewareError { e } | ContractError::ProviderError { e } => e.into(),
+            ContractError::Revert(data) => Self::Reverted(data.to_string()),
+            e => Self::Contract(e),
+        }
+    }
+}
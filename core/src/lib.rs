@@ -4,12 +4,27 @@
 #![allow(clippy::too_many_lines)]
 
 mod collections;
+mod contracts;
 pub mod db;
+mod dead_letters;
+pub mod eip712;
+mod error;
+pub mod metrics;
 mod mints;
+mod outbox;
+mod pending_transfers;
+mod processed_events;
 mod services;
+pub mod validation;
 pub use collections::Collection;
+pub use contracts::Contract;
+pub use dead_letters::{DeadLetter, DeadLetterKey};
+pub use error::ProcessorError;
 use hub_core::prelude::*;
 pub use mints::Mint;
+pub use outbox::Outbox;
+pub use pending_transfers::PendingTransfer;
+pub use processed_events::ProcessedEvent;
 pub use sea_orm;
 pub use services::Services;
 
@@ -0,0 +1,118 @@
+//! Prometheus metrics shared by the consumer and the indexer, registered in the default
+//! registry and exported by each binary on `/metrics`
+
+use std::{future::Future, time::Instant};
+
+use hub_core::prelude::*;
+use once_cell::sync::Lazy;
+use prometheus::{
+    register_histogram_vec, register_int_counter, register_int_counter_vec, Encoder, HistogramVec,
+    IntCounter, IntCounterVec, TextEncoder,
+};
+
+/// Consumer events processed successfully, by event variant
+pub static EVENTS_PROCESSED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "polygon_events_processed_total",
+        "Consumer events processed successfully",
+        &["event"]
+    )
+    .expect("metric is registered once")
+});
+
+/// Consumer events given up on, by event variant and failure reason
+pub static EVENTS_FAILED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "polygon_events_failed_total",
+        "Consumer events given up on after retrying",
+        &["event", "reason"]
+    )
+    .expect("metric is registered once")
+});
+
+/// Time spent processing a consumer event including retries, by event variant and outcome
+pub static EVENT_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "polygon_event_processing_seconds",
+        "Time spent processing a consumer event including retries",
+        &["event", "outcome"]
+    )
+    .expect("metric is registered once")
+});
+
+/// Latency of RPC calls made to the edition contract and its node, by method and outcome
+pub static RPC_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "polygon_rpc_request_seconds",
+        "Latency of RPC calls made to the edition contract and its node",
+        &["method", "outcome"]
+    )
+    .expect("metric is registered once")
+});
+
+/// Alchemy webhook requests received by the indexer, by webhook event type
+pub static WEBHOOK_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "polygon_webhook_requests_total",
+        "Webhook requests received by the indexer",
+        &["event_type"]
+    )
+    .expect("metric is registered once")
+});
+
+/// Webhook requests rejected because their signature did not match
+pub static WEBHOOK_SIGNATURE_FAILURES: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "polygon_webhook_signature_failures_total",
+        "Webhook requests rejected because their signature did not match"
+    )
+    .expect("metric is registered once")
+});
+
+/// Mints whose owner was updated from an on-chain transfer
+pub static OWNERSHIP_UPDATES: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "polygon_ownership_updates_total",
+        "Mints whose owner was updated from an on-chain transfer"
+    )
+    .expect("metric is registered once")
+});
+
+/// The label recorded for a result
+#[must_use]
+pub fn outcome<T, E>(result: &Result<T, E>) -> &'static str {
+    if result.is_ok() {
+        "ok"
+    } else {
+        "error"
+    }
+}
+
+/// Awaits an RPC call, recording its latency under `method`
+///
+/// # Errors
+/// This function fails if the call fails
+pub async fn time_rpc<T, E>(
+    method: &'static str,
+    call: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let started = Instant::now();
+    let result = call.await;
+
+    RPC_DURATION
+        .with_label_values(&[method, outcome(&result)])
+        .observe(started.elapsed().as_secs_f64());
+
+    result
+}
+
+/// Renders every registered metric in the Prometheus text format
+///
+/// # Errors
+/// This function fails if the metrics cannot be encoded
+pub fn encode() -> Result<String> {
+    let mut buf = Vec::new();
+    TextEncoder::new().encode(&prometheus::gather(), &mut buf)?;
+
+    String::from_utf8(buf).context("metrics are not valid UTF-8")
+}
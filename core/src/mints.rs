@@ -1,11 +1,13 @@
+use std::collections::HashMap;
+
 use holaplex_hub_nfts_polygon_entity::{
     collections::{self, Model as Collection},
     mints::{ActiveModel, Column, Entity, Model, Relation},
     prelude::Collections,
 };
 use sea_orm::{
-    prelude::*, ActiveModelTrait, ColumnTrait, EntityTrait, JoinType, QueryFilter, QuerySelect,
-    RelationTrait,
+    prelude::*, sea_query::Expr, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait,
+    EntityTrait, JoinType, QueryFilter, QuerySelect, RelationTrait, TransactionTrait,
 };
 
 use crate::db::Connection;
@@ -17,21 +19,86 @@ impl Mint {
     ///
     /// # Errors
     /// This function fails if ...
-    pub async fn create(db: &Connection, model: Model) -> Result<Model, DbErr> {
-        let conn = db.get();
-
+    pub async fn create<C: ConnectionTrait>(conn: &C, model: Model) -> Result<Model, DbErr> {
         let active_model: ActiveModel = model.into();
 
         active_model.insert(conn).await
     }
 
+    /// Records a mint and reserves its amount against the remaining supply of its drop in
+    /// one transaction. Returns `None` without recording anything when the drop does not
+    /// have enough supply left.
+    ///
+    /// # Errors
+    /// This function fails if the transaction cannot be committed
+    pub async fn create_within_supply<C: ConnectionTrait + TransactionTrait>(
+        conn: &C,
+        model: Model,
+    ) -> Result<Option<Model>, DbErr> {
+        let txn = conn.begin().await?;
+
+        if !reserve_supply(&txn, model.collection_id, model.amount.into()).await? {
+            return Ok(None);
+        }
+
+        let active_model: ActiveModel = model.into();
+        let mint = active_model.insert(&txn).await?;
+
+        txn.commit().await?;
+
+        Ok(Some(mint))
+    }
+
+    /// Records every mint and reserves their amounts against the remaining supply of their
+    /// drops in one transaction. Returns `false` without recording anything when any drop
+    /// does not have enough supply left.
+    ///
+    /// # Errors
+    /// This function fails if the transaction cannot be committed
+    pub async fn create_many<C: ConnectionTrait + TransactionTrait>(
+        conn: &C,
+        models: Vec<Model>,
+    ) -> Result<bool, DbErr> {
+        let txn = conn.begin().await?;
+
+        let mut amounts: HashMap<Uuid, i64> = HashMap::new();
+
+        for model in &models {
+            *amounts.entry(model.collection_id).or_default() += i64::from(model.amount);
+        }
+
+        for (collection_id, amount) in amounts {
+            if !reserve_supply(&txn, collection_id, amount).await? {
+                return Ok(false);
+            }
+        }
+
+        for model in models {
+            let active_model: ActiveModel = model.into();
+            active_model.insert(&txn).await?;
+        }
+
+        txn.commit().await?;
+
+        Ok(true)
+    }
+
     /// Res
     ///
     /// # Errors
     /// This function fails if ...
-    pub async fn find_by_id(db: &Connection, id: Uuid) -> Result<Option<Model>, DbErr> {
-        let conn = db.get();
+    pub async fn update<C: ConnectionTrait>(conn: &C, am: ActiveModel) -> Result<Model, DbErr> {
+        am.update(conn).await
+    }
 
+    /// Res
+    ///
+    /// # Errors
+    /// This function fails if ...
+    pub async fn find_by_id<C: ConnectionTrait>(
+        conn: &C,
+        id: Uuid,
+    ) -> Result<Option<Model>, DbErr> {
         Entity::find().filter(Column::Id.eq(id)).one(conn).await
     }
 
@@ -39,22 +106,19 @@ impl Mint {
     ///
     /// # Errors
     /// This function fails if ...
-    pub async fn find_with_collection(
-        db: &Connection,
+    pub async fn find_with_collection<C: ConnectionTrait>(
+        conn: &C,
         id: Uuid,
-    ) -> Result<(Model, Option<Collection>), DbErr> {
-        let conn: &DatabaseConnection = db.get();
-
-        let mint = Entity::find_by_id(id)
-            .one(conn)
-            .await?
-            .ok_or(DbErr::Custom("Mint not found".to_owned()))?;
+    ) -> Result<Option<(Model, Option<Collection>)>, DbErr> {
+        let Some(mint) = Entity::find_by_id(id).one(conn).await? else {
+            return Ok(None);
+        };
 
         let collection = Collections::find_by_id(mint.collection_id)
             .one(conn)
             .await?;
 
-        Ok((mint, collection))
+        Ok(Some((mint, collection)))
     }
 
     /// Res
@@ -71,8 +135,32 @@ impl Mint {
             .join(JoinType::InnerJoin, Relation::Collection.def())
             .filter(collections::Column::EditionId.eq(edition_id))
             .filter(Column::Owner.eq(owner))
+            .filter(Column::BurnedAt.is_null())
             .limit(Some(value))
             .all(db.get())
             .await
     }
 }
+
+/// Adds `amount` to the minted count of a drop unless that would exceed its supply. Drops
+/// created before supply was tracked have no supply and are never limited.
+async fn reserve_supply<C: ConnectionTrait>(
+    conn: &C,
+    collection_id: Uuid,
+    amount: i64,
+) -> Result<bool, DbErr> {
+    let minted = Expr::col(collections::Column::Minted).add(amount);
+
+    let res = Collections::update_many()
+        .col_expr(collections::Column::Minted, minted.clone())
+        .filter(collections::Column::Id.eq(collection_id))
+        .filter(
+            Condition::any()
+                .add(collections::Column::Supply.is_null())
+                .add(Expr::expr(minted).lte(Expr::col(collections::Column::Supply))),
+        )
+        .exec(conn)
+        .await?;
+
+    Ok(res.rows_affected == 1)
+}
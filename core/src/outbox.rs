@@ -0,0 +1,94 @@
+use holaplex_hub_nfts_polygon_entity::outbox::{ActiveModel, Column, Entity, Model};
+use hub_core::chrono::Utc;
+use prost::Message;
+use sea_orm::{prelude::*, sea_query::Expr, ConnectionTrait, QueryOrder, QuerySelect, Statement};
+
+use crate::proto::{PolygonNftEventKey, PolygonNftEvents};
+
+pub struct Outbox;
+
+impl Outbox {
+    /// Queues `event` for publishing. Written in the same transaction as the change that
+    /// produced the event so the two are committed or rolled back together.
+    ///
+    /// # Errors
+    /// This function fails if the row cannot be inserted
+    pub async fn create<C: ConnectionTrait>(
+        conn: &C,
+        key: &PolygonNftEventKey,
+        event: &PolygonNftEvents,
+    ) -> Result<Model, DbErr> {
+        let active_model: ActiveModel = Model {
+            id: Uuid::new_v4(),
+            key: key.encode_to_vec(),
+            payload: event.encode_to_vec(),
+            created_at: Utc::now().naive_utc(),
+            failed_at: None,
+        }
+        .into();
+
+        active_model.insert(conn).await
+    }
+
+    /// Tries to take the transaction scoped advisory lock that lets a single relay publish
+    /// at a time, so events leave in the order they were queued across replicas. Returns
+    /// `false` without waiting if another relay holds it.
+    ///
+    /// # Errors
+    /// This function fails if the lock cannot be requested
+    pub async fn try_lock_relay<C: ConnectionTrait>(conn: &C) -> Result<bool, DbErr> {
+        let row = conn
+            .query_one(Statement::from_string(
+                conn.get_database_backend(),
+                "SELECT pg_try_advisory_xact_lock(hashtextextended('outbox-relay', 0)) AS locked"
+                    .to_owned(),
+            ))
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("advisory lock result".to_owned()))?;
+
+        row.try_get("", "locked")
+    }
+
+    /// The `limit` oldest rows that are neither sent nor failed. Must be called while
+    /// holding the relay lock.
+    ///
+    /// # Errors
+    /// This function fails if the rows cannot be read
+    pub async fn find_pending<C: ConnectionTrait>(
+        conn: &C,
+        limit: u64,
+    ) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::FailedAt.is_null())
+            .order_by_asc(Column::CreatedAt)
+            .limit(limit)
+            .all(conn)
+            .await
+    }
+
+    /// Removes rows once Kafka acknowledged their events
+    ///
+    /// # Errors
+    /// This function fails if the rows cannot be deleted
+    pub async fn delete_sent<C: ConnectionTrait>(conn: &C, ids: Vec<Uuid>) -> Result<(), DbErr> {
+        Entity::delete_many()
+            .filter(Column::Id.is_in(ids))
+            .exec(conn)
+            .await
+            .map(|_| ())
+    }
+
+    /// Marks rows that cannot be decoded so they stay for inspection without blocking the
+    /// rows queued after them
+    ///
+    /// # Errors
+    /// This function fails if the rows cannot be updated
+    pub async fn mark_failed<C: ConnectionTrait>(conn: &C, ids: Vec<Uuid>) -> Result<(), DbErr> {
+        Entity::update_many()
+            .col_expr(Column::FailedAt, Expr::value(Utc::now().naive_utc()))
+            .filter(Column::Id.is_in(ids))
+            .exec(conn)
+            .await
+            .map(|_| ())
+    }
+}
@@ -0,0 +1,35 @@
+use holaplex_hub_nfts_polygon_entity::pending_transfers::{ActiveModel, Column, Entity, Model};
+use sea_orm::{prelude::*, ConnectionTrait};
+
+pub struct PendingTransfer;
+
+impl PendingTransfer {
+    /// Res
+    ///
+    /// # Errors
+    /// This function fails if ...
+    pub async fn create<C: ConnectionTrait>(conn: &C, model: Model) -> Result<Model, DbErr> {
+        let active_model: ActiveModel = model.into();
+
+        active_model.insert(conn).await
+    }
+
+    /// Res
+    ///
+    /// # Errors
+    /// This function fails if ...
+    pub async fn update<C: ConnectionTrait>(conn: &C, am: ActiveModel) -> Result<Model, DbErr> {
+        am.update(conn).await
+    }
+
+    /// Res
+    ///
+    /// # Errors
+    /// This function fails if ...
+    pub async fn find_by_id<C: ConnectionTrait>(
+        conn: &C,
+        id: Uuid,
+    ) -> Result<Option<Model>, DbErr> {
+        Entity::find().filter(Column::Id.eq(id)).one(conn).await
+    }
+}
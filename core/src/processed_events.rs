@@ -0,0 +1,51 @@
+use holaplex_hub_nfts_polygon_entity::processed_events::{ActiveModel, Entity, Model};
+use sea_orm::{prelude::*, ConnectionTrait, Statement};
+
+pub struct ProcessedEvent;
+
+impl ProcessedEvent {
+    /// Res
+    ///
+    /// # Errors
+    /// This function fails if ...
+    pub async fn create<C: ConnectionTrait>(conn: &C, model: Model) -> Result<Model, DbErr> {
+        let active_model: ActiveModel = model.into();
+
+        active_model.insert(conn).await
+    }
+
+    /// Takes a transaction scoped advisory lock on the ledger key, so concurrent deliveries
+    /// of the same message wait for each other instead of racing past [`Self::find`]
+    ///
+    /// # Errors
+    /// This function fails if the lock cannot be taken
+    pub async fn lock<C: ConnectionTrait>(
+        conn: &C,
+        topic: &str,
+        key_id: &str,
+        event: &str,
+    ) -> Result<(), DbErr> {
+        conn.execute(Statement::from_sql_and_values(
+            conn.get_database_backend(),
+            "SELECT pg_advisory_xact_lock(hashtextextended($1, 0))",
+            [format!("{topic}/{key_id}/{event}").into()],
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    /// Res
+    ///
+    /// # Errors
+    /// This function fails if ...
+    pub async fn find<C: ConnectionTrait>(
+        conn: &C,
+        topic: &str,
+        key_id: &str,
+        event: &str,
+    ) -> Result<Option<Model>, DbErr> {
+        Entity::find_by_id((topic.to_owned(), key_id.to_owned(), event.to_owned()))
+            .one(conn)
+            .await
+    }
+}
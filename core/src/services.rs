@@ -3,12 +3,41 @@ use prost::Message;
 
 use crate::proto::{self, PolygonNftEventKey, PolygonNftEvents};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Services {
     Nfts(proto::NftEventKey, proto::NftEvents),
     Treasuries(proto::TreasuryEventKey, proto::TreasuryEvents),
 }
 
+impl Services {
+    /// The Kafka topic the message was consumed from
+    #[must_use]
+    pub fn topic(&self) -> &'static str {
+        match self {
+            Self::Nfts(..) => "hub-nfts",
+            Self::Treasuries(..) => "hub-treasuries",
+        }
+    }
+
+    /// The id carried by the message key
+    #[must_use]
+    pub fn key_id(&self) -> &str {
+        match self {
+            Self::Nfts(key, _) => &key.id,
+            Self::Treasuries(key, _) => &key.id,
+        }
+    }
+
+    /// Re-encodes the message into its protobuf key and payload
+    #[must_use]
+    pub fn encode(&self) -> (Vec<u8>, Vec<u8>) {
+        match self {
+            Self::Nfts(key, e) => (key.encode_to_vec(), e.encode_to_vec()),
+            Self::Treasuries(key, e) => (key.encode_to_vec(), e.encode_to_vec()),
+        }
+    }
+}
+
 impl hub_core::consumer::MessageGroup for Services {
     const REQUESTED_TOPICS: &'static [&'static str] = &["hub-nfts", "hub-treasuries"];
 
@@ -0,0 +1,429 @@
+//! Checks applied to edition metadata and addresses received from hub-nfts before any
+//! state is changed on their behalf.
+
+use std::fmt;
+
+use ethers::{types::Address, utils::to_checksum};
+
+use crate::proto::EditionInfo;
+
+/// The longest accepted collection name, in bytes
+pub const MAX_NAME_LEN: usize = 200;
+/// The longest accepted collection description, in bytes
+pub const MAX_DESCRIPTION_LEN: usize = 5_000;
+/// The longest accepted metadata or image URI, in bytes
+pub const MAX_URI_LEN: usize = 2_048;
+/// The royalty denominator of the edition contract, i.e. 100%
+pub const MAX_ROYALTY_BPS: u16 = 10_000;
+
+const URI_SCHEMES: [&str; 3] = ["ipfs", "ar", "https"];
+
+/// Why a single field was rejected
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Violation {
+    #[error("must not be empty")]
+    Empty,
+    #[error("must be at most {0} bytes long")]
+    TooLong(usize),
+    #[error("must be an ipfs://, ar:// or https:// URI")]
+    UnsupportedUri,
+    #[error("must be a 0x-prefixed 20 byte hex address")]
+    InvalidAddress,
+    #[error("must be EIP-55 checksummed as {0}")]
+    Checksum(String),
+    #[error("must be between 0 and {0} basis points")]
+    OutOfRange(u16),
+}
+
+/// A violation together with the payload field it was found in
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{field} {violation}")]
+pub struct FieldError {
+    pub field: &'static str,
+    pub violation: Violation,
+}
+
+/// Every field rejected while validating a payload
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub struct ValidationErrors(pub Vec<FieldError>);
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{error}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects field errors so a payload is reported with all of its problems at once
+#[derive(Debug, Default)]
+pub struct Validator {
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn reject(&mut self, field: &'static str, violation: Violation) {
+        self.errors.push(FieldError { field, violation });
+    }
+
+    /// Checks that `value` is not blank and at most `max` bytes long
+    pub fn text(&mut self, field: &'static str, value: &str, max: usize) -> &mut Self {
+        if value.trim().is_empty() {
+            self.reject(field, Violation::Empty);
+        } else {
+            self.length(field, value, max);
+        }
+
+        self
+    }
+
+    /// Checks that `value` is at most `max` bytes long, allowing it to be empty
+    pub fn length(&mut self, field: &'static str, value: &str, max: usize) -> &mut Self {
+        if value.len() > max {
+            self.reject(field, Violation::TooLong(max));
+        }
+
+        self
+    }
+
+    /// Checks that `value` is a URI with one of the supported schemes
+    pub fn uri(&mut self, field: &'static str, value: &str) -> &mut Self {
+        if value.is_empty() {
+            self.reject(field, Violation::Empty);
+        } else if value.len() > MAX_URI_LEN {
+            self.reject(field, Violation::TooLong(MAX_URI_LEN));
+        } else if !supported_uri(value) {
+            self.reject(field, Violation::UnsupportedUri);
+        }
+
+        self
+    }
+
+    /// Parses `value` as an EIP-55 checksummed address.
+    ///
+    /// The zero address is returned if `value` is rejected, it must only be used once
+    /// [`finish`](Self::finish) succeeded.
+    pub fn address(&mut self, field: &'static str, value: &str) -> Address {
+        checksummed_address(value).unwrap_or_else(|violation| {
+            self.reject(field, violation);
+
+            Address::zero()
+        })
+    }
+
+    /// Parses `value` as a royalty in basis points.
+    ///
+    /// Zero is returned if `value` is rejected, it must only be used once
+    /// [`finish`](Self::finish) succeeded.
+    pub fn royalty_bps(&mut self, field: &'static str, value: u32) -> u16 {
+        match u16::try_from(value) {
+            Ok(bps) if bps <= MAX_ROYALTY_BPS => bps,
+            _ => {
+                self.reject(field, Violation::OutOfRange(MAX_ROYALTY_BPS));
+
+                0
+            },
+        }
+    }
+
+    /// Checks every field of the edition metadata
+    pub fn edition_info(&mut self, info: &EditionInfo) -> &mut Self {
+        let EditionInfo {
+            description,
+            image_uri,
+            collection,
+            uri,
+            creator,
+        } = info;
+
+        self.text("edition_info.collection", collection, MAX_NAME_LEN)
+            .length("edition_info.description", description, MAX_DESCRIPTION_LEN)
+            .uri("edition_info.uri", uri)
+            .uri("edition_info.image_uri", image_uri);
+        self.address("edition_info.creator", creator);
+
+        self
+    }
+
+    /// Finishes validation, failing if any field was rejected
+    ///
+    /// # Errors
+    /// This function fails if any of the checked fields was invalid
+    pub fn finish(&mut self) -> Result<(), ValidationErrors> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationErrors(std::mem::take(&mut self.errors)))
+        }
+    }
+}
+
+fn supported_uri(value: &str) -> bool {
+    let Some((scheme, rest)) = value.split_once("://") else {
+        return false;
+    };
+
+    URI_SCHEMES.contains(&scheme)
+        && !rest.is_empty()
+        && !rest.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+fn checksummed_address(value: &str) -> Result<Address, Violation> {
+    let hex = value
+        .strip_prefix("0x")
+        .filter(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or(Violation::InvalidAddress)?;
+    let address: Address = hex.parse().map_err(|_| Violation::InvalidAddress)?;
+    let checksummed = to_checksum(&address, None);
+
+    if checksummed == value {
+        Ok(address)
+    } else {
+        Err(Violation::Checksum(checksummed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Checksummed examples from EIP-55
+    const CHECKSUMMED: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+    const OTHER_CHECKSUMMED: &str = "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359";
+    const VALID: [Violation; 0] = [];
+
+    fn violations(validator: &mut Validator) -> Vec<Violation> {
+        validator.finish().map_or_else(
+            |ValidationErrors(errors)| errors.into_iter().map(|e| e.violation).collect(),
+            |()| Vec::new(),
+        )
+    }
+
+    fn address_violations(value: &str) -> Vec<Violation> {
+        let mut validator = Validator::new();
+        validator.address("address", value);
+
+        violations(&mut validator)
+    }
+
+    fn uri_violations(value: &str) -> Vec<Violation> {
+        violations(Validator::new().uri("uri", value))
+    }
+
+    #[test]
+    fn accepts_checksummed_addresses() {
+        for value in [CHECKSUMMED, OTHER_CHECKSUMMED] {
+            let mut validator = Validator::new();
+            let address = validator.address("address", value);
+
+            assert_eq!(validator.finish(), Ok(()));
+            assert_eq!(address, value.parse::<Address>().unwrap());
+        }
+    }
+
+    #[test]
+    fn rejects_addresses_in_the_wrong_case() {
+        let checksum = Violation::Checksum(CHECKSUMMED.to_owned());
+
+        assert_eq!(address_violations(&CHECKSUMMED.to_lowercase()), [
+            checksum.clone()
+        ]);
+        assert_eq!(
+            address_violations(&format!("0x{}", CHECKSUMMED[2..].to_uppercase())),
+            [checksum.clone()]
+        );
+        assert_eq!(
+            address_violations("0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            [checksum]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_addresses() {
+        for value in [
+            "",
+            "0x",
+            &CHECKSUMMED[2..],
+            &CHECKSUMMED[..41],
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAedd",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg",
+        ] {
+            assert_eq!(
+                address_violations(value),
+                [Violation::InvalidAddress],
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejected_addresses_parse_as_zero() {
+        let mut validator = Validator::new();
+
+        assert_eq!(validator.address("address", "nope"), Address::zero());
+        assert!(validator.finish().is_err());
+    }
+
+    #[test]
+    fn accepts_each_uri_scheme() {
+        for value in [
+            "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+            "ar://bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U",
+            "https://example.com/metadata/1.json",
+        ] {
+            assert_eq!(uri_violations(value), VALID, "{value}");
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_uris() {
+        for value in [
+            "http://example.com/1.json",
+            "example.com/1.json",
+            "ipfs://",
+            "ar://",
+            "https://",
+            "ipfs://bafy beig",
+            "https://example.com/\t1.json",
+            "https://example.com/1.json\n",
+        ] {
+            assert_eq!(
+                uri_violations(value),
+                [Violation::UnsupportedUri],
+                "{value:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn limits_uri_length() {
+        let longest = format!("https://{}", "a".repeat(MAX_URI_LEN - "https://".len()));
+
+        assert_eq!(uri_violations(&longest), VALID);
+        assert_eq!(uri_violations(&format!("{longest}a")), [
+            Violation::TooLong(MAX_URI_LEN)
+        ]);
+        assert_eq!(uri_violations(""), [Violation::Empty]);
+    }
+
+    #[test]
+    fn limits_text_length() {
+        let name = "a".repeat(MAX_NAME_LEN);
+
+        assert_eq!(
+            violations(Validator::new().text("name", &name, MAX_NAME_LEN)),
+            VALID
+        );
+        assert_eq!(
+            violations(Validator::new().text("name", &format!("{name}a"), MAX_NAME_LEN)),
+            [Violation::TooLong(MAX_NAME_LEN)]
+        );
+        assert_eq!(
+            violations(Validator::new().text("name", " \n", MAX_NAME_LEN)),
+            [Violation::Empty]
+        );
+    }
+
+    #[test]
+    fn limits_length_allowing_empty_values() {
+        let description = "a".repeat(MAX_DESCRIPTION_LEN);
+
+        assert_eq!(
+            violations(Validator::new().length("description", "", MAX_DESCRIPTION_LEN)),
+            VALID
+        );
+        assert_eq!(
+            violations(Validator::new().length("description", &description, MAX_DESCRIPTION_LEN)),
+            VALID
+        );
+        assert_eq!(
+            violations(Validator::new().length(
+                "description",
+                &format!("{description}a"),
+                MAX_DESCRIPTION_LEN
+            )),
+            [Violation::TooLong(MAX_DESCRIPTION_LEN)]
+        );
+    }
+
+    #[test]
+    fn bounds_royalty_basis_points() {
+        for (value, expected) in [(0, 0), (500, 500), (10_000, MAX_ROYALTY_BPS)] {
+            let mut validator = Validator::new();
+
+            assert_eq!(validator.royalty_bps("royalty", value), expected);
+            assert_eq!(validator.finish(), Ok(()));
+        }
+
+        for value in [
+            10_001,
+            u32::from(u16::MAX),
+            u32::from(u16::MAX) + 1,
+            u32::MAX,
+        ] {
+            let mut validator = Validator::new();
+
+            assert_eq!(validator.royalty_bps("royalty", value), 0);
+            assert_eq!(violations(&mut validator), [Violation::OutOfRange(
+                MAX_ROYALTY_BPS
+            )]);
+        }
+    }
+
+    #[test]
+    fn finish_collects_every_field_error() {
+        let info = EditionInfo {
+            description: "a".repeat(MAX_DESCRIPTION_LEN + 1),
+            image_uri: "http://example.com/1.png".to_owned(),
+            collection: String::new(),
+            uri: String::new(),
+            creator: CHECKSUMMED.to_lowercase(),
+        };
+
+        let mut validator = Validator::new();
+        validator.edition_info(&info);
+        validator.royalty_bps("fee_numerator", 10_001);
+
+        assert_eq!(
+            validator.finish(),
+            Err(ValidationErrors(vec![
+                FieldError {
+                    field: "edition_info.collection",
+                    violation: Violation::Empty,
+                },
+                FieldError {
+                    field: "edition_info.description",
+                    violation: Violation::TooLong(MAX_DESCRIPTION_LEN),
+                },
+                FieldError {
+                    field: "edition_info.uri",
+                    violation: Violation::Empty,
+                },
+                FieldError {
+                    field: "edition_info.image_uri",
+                    violation: Violation::UnsupportedUri,
+                },
+                FieldError {
+                    field: "edition_info.creator",
+                    violation: Violation::Checksum(CHECKSUMMED.to_owned()),
+                },
+                FieldError {
+                    field: "fee_numerator",
+                    violation: Violation::OutOfRange(MAX_ROYALTY_BPS),
+                },
+            ]))
+        );
+        assert_eq!(validator.finish(), Ok(()));
+    }
+}
@@ -31,6 +31,17 @@ pub struct Model {
     pub description: String,
     pub image_uri: String,
     pub created_at: DateTime,
+    #[sea_orm(
+        column_type = "custom(\"citext\")",
+        select_as = "text",
+        save_as = "citext"
+    )]
+    pub contract_address: String,
+    #[sea_orm(column_type = "Text")]
+    pub network: String,
+    pub supply: Option<i64>,
+    pub minted: i64,
+    pub fee_numerator: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
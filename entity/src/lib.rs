@@ -1,4 +1,8 @@
 pub mod collections;
+pub mod contracts;
 pub mod mints;
+pub mod outbox;
+pub mod pending_transfers;
+pub mod processed_events;
 
 pub mod prelude;
@@ -16,6 +16,9 @@ pub struct Model {
     pub owner: String,
     pub amount: i32,
     pub created_at: DateTime,
+    pub burned_at: Option<DateTime>,
+    #[sea_orm(column_type = "Text")]
+    pub network: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -28,6 +31,8 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Collection,
+    #[sea_orm(has_many = "super::pending_transfers::Entity")]
+    PendingTransfers,
 }
 
 impl Related<super::collections::Entity> for Entity {
@@ -36,4 +41,10 @@ impl Related<super::collections::Entity> for Entity {
     }
 }
 
+impl Related<super::pending_transfers::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PendingTransfers.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
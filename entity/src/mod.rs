@@ -3,4 +3,8 @@
 pub mod prelude;
 
 pub mod collections;
+pub mod contracts;
 pub mod mints;
+pub mod outbox;
+pub mod pending_transfers;
+pub mod processed_events;
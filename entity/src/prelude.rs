@@ -1,3 +1,7 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.11.3
 
-pub use super::{collections::Entity as Collections, mints::Entity as Mints};
+pub use super::{
+    collections::Entity as Collections, contracts::Entity as Contracts, mints::Entity as Mints,
+    outbox::Entity as Outbox, pending_transfers::Entity as PendingTransfers,
+    processed_events::Entity as ProcessedEvents,
+};
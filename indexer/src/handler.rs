@@ -2,6 +2,7 @@ use std::str::FromStr;
 
 use holaplex_hub_nfts_polygon_core::{
     db::Connection,
+    metrics::{self, OWNERSHIP_UPDATES, WEBHOOK_REQUESTS, WEBHOOK_SIGNATURE_FAILURES},
     proto::{
         polygon_nft_events::Event, MintedTokensOwnershipUpdate, PolygonNftEventKey,
         PolygonNftEvents,
@@ -12,12 +13,13 @@ use holaplex_hub_nfts_polygon_core::{
 use holaplex_hub_nfts_polygon_entity::mints;
 use hub_core::{
     anyhow::{self, Context},
+    chrono::Utc,
     futures_util::future::try_join_all,
     prelude::anyhow,
     producer::Producer,
     prost_types::Timestamp,
     serde_json,
-    tracing::info,
+    tracing::{field, info, info_span, Instrument, Span},
 };
 use poem::{handler, web::Data, Request, Result};
 
@@ -35,6 +37,11 @@ pub async fn process(
     processor.process_payload(signature, payload).await
 }
 
+#[handler]
+pub fn export_metrics() -> Result<String> {
+    metrics::encode().map_err(Into::into)
+}
+
 #[derive(Clone)]
 pub struct NftActivityController {
     db: Connection,
@@ -64,14 +71,38 @@ impl NftActivityController {
 
         let ts = Timestamp::from_str(&payload.created_at).context("failed to parse timestamp")?;
 
-        bytes.verify(&signature, &self.signing_key)?;
+        let event_type = format!("{:?}", payload.ty);
+        WEBHOOK_REQUESTS
+            .with_label_values(&[event_type.as_str()])
+            .inc();
+
+        let span = info_span!(
+            "webhook",
+            webhook_id = %payload.webhook_id,
+            id = %payload.id,
+            event_type = %event_type,
+        );
+
+        async move {
+            if let Err(e) = bytes.verify(&signature, &self.signing_key) {
+                WEBHOOK_SIGNATURE_FAILURES.inc();
 
-        if payload.ty == EventType::NftActivity {
-            for event in payload.event.activity {
-                self.process_nft_activity(event, &ts).await?;
+                return Err(e);
             }
+
+            if payload.ty == EventType::NftActivity {
+                for event in payload.event.activity {
+                    let span = info_span!("nft_activity", transaction_hash = %event.hash);
+
+                    self.process_nft_activity(event, &ts)
+                        .instrument(span)
+                        .await?;
+                }
+            }
+            Ok(())
         }
-        Ok(())
+        .instrument(span)
+        .await
     }
 
     async fn process_nft_activity(&self, event: ActivityPayload, ts: &Timestamp) -> Result<()> {
@@ -92,34 +123,53 @@ impl NftActivityController {
                 let event = event.clone();
                 let self_cloned = self.clone();
 
-                tokio::spawn(async move {
-                    let edition_id = strip_prefix(token.token_id)?;
-                    let value = strip_prefix(token.value)?;
-                    info!("edition_id: {:?}", edition_id);
-                    info!("value: {:?}", value);
+                let span = info_span!("ownership_update", edition_id = field::Empty);
 
-                    let mints =
-                        Mint::get_mints_for_edition(&db, &event.from_address, edition_id, value)
-                            .await
-                            .context("failed to get mints")?;
+                tokio::spawn(
+                    async move {
+                        let edition_id = strip_prefix(token.token_id)?;
+                        Span::current().record("edition_id", edition_id);
+                        let value = strip_prefix(token.value)?;
+                        info!("edition_id: {:?}", edition_id);
+                        info!("value: {:?}", value);
 
-                    if mints.len() != value as usize {
-                        return Err(anyhow!(
-                            "Expected {} mints for edition {}, but found {}",
-                            value,
+                        let mints = Mint::get_mints_for_edition(
+                            &db,
+                            &event.from_address,
                             edition_id,
-                            mints.len()
+                            value,
                         )
-                        .into());
-                    }
-
-                    self_cloned
-                        .update_mints_owner(&mints, &event.to_address)
-                        .await?;
-                    self_cloned
-                        .emit_event(&mints, &event.to_address, &ts, &event.hash)
                         .await
-                })
+                        .context("failed to get mints")?;
+
+                        if mints.len() != value as usize {
+                            return Err(anyhow!(
+                                "Expected {} mints for edition {}, but found {}",
+                                value,
+                                edition_id,
+                                mints.len()
+                            )
+                            .into());
+                        }
+
+                        // Burns are transfers to the null address. Their mints are marked
+                        // burned only now that the burn is confirmed on-chain.
+                        if event.to_address == NULL_ADDRESS {
+                            return self_cloned
+                                .mark_mints_burned(&mints)
+                                .await
+                                .map_err(Into::into);
+                        }
+
+                        self_cloned
+                            .update_mints_owner(&mints, &event.to_address)
+                            .await?;
+                        self_cloned
+                            .emit_event(&mints, &event.to_address, &ts, &event.hash)
+                            .await
+                    }
+                    .instrument(span),
+                )
             })
             .collect::<Vec<_>>();
 
@@ -188,7 +238,31 @@ impl NftActivityController {
 
         txn.commit()
             .await
-            .map_err(|e| anyhow!(format!("failed to update mints {e}")))
+            .map_err(|e| anyhow!(format!("failed to update mints {e}")))?;
+
+        OWNERSHIP_UPDATES.inc_by(mints.len().try_into()?);
+
+        Ok(())
+    }
+
+    async fn mark_mints_burned(&self, mints: &[mints::Model]) -> anyhow::Result<()> {
+        let txn = self.db.get().begin().await?;
+        let now = Utc::now().naive_utc();
+
+        for mint in mints {
+            let mut mint_am: mints::ActiveModel = mint.clone().into();
+            mint_am.burned_at = Set(Some(now));
+            mint_am
+                .update(&txn)
+                .await
+                .context("failed to update mint")?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| anyhow!(format!("failed to mark mints burned {e}")))?;
+
+        Ok(())
     }
 }
 
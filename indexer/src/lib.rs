@@ -3,7 +3,7 @@ mod handler;
 mod types;
 
 use error::Error;
-pub use handler::{process, NftActivityController};
+pub use handler::{export_metrics, process, NftActivityController};
 use hmac::{Hmac, Mac};
 use holaplex_hub_nfts_polygon_core::db::DbArgs;
 use hub_core::{anyhow::Context, clap};
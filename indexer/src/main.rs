@@ -1,7 +1,7 @@
 use holaplex_hub_nfts_polygon_core::{db::Connection, proto::PolygonNftEvents};
-use holaplex_hub_nfts_polygon_indexer::{process, Args, NftActivityController};
+use holaplex_hub_nfts_polygon_indexer::{export_metrics, process, Args, NftActivityController};
 use hub_core::anyhow::Context;
-use poem::{listener::TcpListener, middleware::AddData, post, EndpointExt, Route, Server};
+use poem::{get, listener::TcpListener, middleware::AddData, post, EndpointExt, Route, Server};
 
 pub fn main() {
     let opts = hub_core::StartConfig {
@@ -32,7 +32,9 @@ pub fn main() {
             let processor =
                 NftActivityController::new(connection, producer, contract_deployer, signing_key);
 
-            let app = Route::new().at("/", post(process).with(AddData::new(processor)));
+            let app = Route::new()
+                .at("/", post(process).with(AddData::new(processor)))
+                .at("/metrics", get(export_metrics));
             Server::new(TcpListener::bind(format!("0.0.0.0:{port}")))
                 .run(app)
                 .await
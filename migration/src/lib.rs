@@ -4,6 +4,20 @@ mod m20230608_110420_create_collections_table;
 mod m20230608_110425_create_mints_table;
 mod m20230618_140219_add_name_to_collections;
 mod m20230710_195615_change_address_columns_to_citext;
+mod m20230725_120314_create_processed_events_table;
+mod m20230726_093142_create_edition_id_sequence;
+mod m20230727_151208_create_pending_transfers_table;
+mod m20230728_104533_add_submitted_at_to_pending_transfers;
+mod m20230731_141022_add_burned_at_to_mints;
+mod m20230801_103417_create_contracts_table;
+mod m20230801_103925_add_contract_address_to_collections;
+mod m20230802_112846_add_network_to_collections_mints_and_contracts;
+mod m20230803_094210_add_supply_and_minted_to_collections;
+mod m20230804_150631_add_fee_numerator_to_collections;
+mod m20230807_101552_create_outbox_table;
+mod m20230808_093417_backfill_collections_contract_address;
+mod m20230808_141530_drop_payload_from_processed_events;
+mod m20230809_102214_replace_sent_at_with_failed_at_in_outbox;
 
 pub struct Migrator;
 
@@ -15,6 +29,20 @@ impl MigratorTrait for Migrator {
             Box::new(m20230608_110425_create_mints_table::Migration),
             Box::new(m20230618_140219_add_name_to_collections::Migration),
             Box::new(m20230710_195615_change_address_columns_to_citext::Migration),
+            Box::new(m20230725_120314_create_processed_events_table::Migration),
+            Box::new(m20230726_093142_create_edition_id_sequence::Migration),
+            Box::new(m20230727_151208_create_pending_transfers_table::Migration),
+            Box::new(m20230728_104533_add_submitted_at_to_pending_transfers::Migration),
+            Box::new(m20230731_141022_add_burned_at_to_mints::Migration),
+            Box::new(m20230801_103417_create_contracts_table::Migration),
+            Box::new(m20230801_103925_add_contract_address_to_collections::Migration),
+            Box::new(m20230802_112846_add_network_to_collections_mints_and_contracts::Migration),
+            Box::new(m20230803_094210_add_supply_and_minted_to_collections::Migration),
+            Box::new(m20230804_150631_add_fee_numerator_to_collections::Migration),
+            Box::new(m20230807_101552_create_outbox_table::Migration),
+            Box::new(m20230808_093417_backfill_collections_contract_address::Migration),
+            Box::new(m20230808_141530_drop_payload_from_processed_events::Migration),
+            Box::new(m20230809_102214_replace_sent_at_with_failed_at_in_outbox::Migration),
         ]
     }
 }
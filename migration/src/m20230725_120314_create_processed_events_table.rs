@@ -0,0 +1,50 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProcessedEvents::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ProcessedEvents::Topic).text().not_null())
+                    .col(ColumnDef::new(ProcessedEvents::KeyId).text().not_null())
+                    .col(ColumnDef::new(ProcessedEvents::Event).text().not_null())
+                    .col(ColumnDef::new(ProcessedEvents::Payload).binary())
+                    .col(
+                        ColumnDef::new(ProcessedEvents::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .extra("default now()".to_string()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ProcessedEvents::Topic)
+                            .col(ProcessedEvents::KeyId)
+                            .col(ProcessedEvents::Event),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProcessedEvents::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum ProcessedEvents {
+    Table,
+    Topic,
+    KeyId,
+    Event,
+    Payload,
+    CreatedAt,
+}
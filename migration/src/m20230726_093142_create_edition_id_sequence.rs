@@ -0,0 +1,124 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, Statement},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // The max + 1 edition ids this sequence replaces could be handed out twice. Each
+        // duplicate refers to a single on-chain edition, so which drop keeps it has to be
+        // decided by hand before the unique index can be created.
+        let stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"select edition_id, string_agg(id::text, ', ' order by created_at) as ids
+            from collections group by edition_id having count(*) > 1
+            order by edition_id;"#
+                .to_string(),
+        );
+
+        let duplicates = db
+            .query_all(stmt)
+            .await?
+            .iter()
+            .map(|row| {
+                Ok(format!(
+                    "edition {} is used by collections {}",
+                    row.try_get::<i32>("", "edition_id")?,
+                    row.try_get::<String>("", "ids")?
+                ))
+            })
+            .collect::<Result<Vec<_>, DbErr>>()?;
+
+        if !duplicates.is_empty() {
+            return Err(DbErr::Migration(format!(
+                "collections share edition ids: {}. Keep the collection that owns each \
+                 on-chain edition, move or remove the others, then run this migration again",
+                duplicates.join("; ")
+            )));
+        }
+
+        let stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"create sequence if not exists collections_edition_id_seq
+            as integer owned by collections.edition_id;"#
+                .to_string(),
+        );
+
+        db.execute(stmt).await?;
+
+        let stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"select setval('collections_edition_id_seq',
+            coalesce((select max(edition_id) from collections), 0) + 1, false);"#
+                .to_string(),
+        );
+
+        db.execute(stmt).await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("collections-edition_id-idx")
+                    .table(Collections::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("collections-edition_id-idx")
+                    .table(Collections::Table)
+                    .col(Collections::EditionId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("collections-edition_id-idx")
+                    .table(Collections::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("collections-edition_id-idx")
+                    .table(Collections::Table)
+                    .col(Collections::EditionId)
+                    .index_type(IndexType::BTree)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+
+        let stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"drop sequence if exists collections_edition_id_seq;"#.to_string(),
+        );
+
+        db.execute(stmt).await?;
+
+        Ok(())
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Collections {
+    Table,
+    EditionId,
+}
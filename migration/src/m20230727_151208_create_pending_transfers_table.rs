@@ -0,0 +1,108 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230608_110425_create_mints_table::Mints;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PendingTransfers::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PendingTransfers::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PendingTransfers::CollectionMintId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PendingTransfers::Owner)
+                            .custom(Alias::new("citext"))
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PendingTransfers::Spender)
+                            .custom(Alias::new("citext"))
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PendingTransfers::Recipient)
+                            .custom(Alias::new("citext"))
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PendingTransfers::EditionId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PendingTransfers::Amount)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PendingTransfers::Deadline)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PendingTransfers::Hash).binary().not_null())
+                    .col(
+                        ColumnDef::new(PendingTransfers::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .extra("default now()".to_string()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-pending_transfers_collection_mint_id")
+                            .from(PendingTransfers::Table, PendingTransfers::CollectionMintId)
+                            .to(Mints::Table, Mints::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("pending_transfers_collection_mint_id_idx")
+                    .table(PendingTransfers::Table)
+                    .col(PendingTransfers::CollectionMintId)
+                    .index_type(IndexType::Hash)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PendingTransfers::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum PendingTransfers {
+    Table,
+    Id,
+    CollectionMintId,
+    Owner,
+    Spender,
+    Recipient,
+    EditionId,
+    Amount,
+    Deadline,
+    Hash,
+    CreatedAt,
+}
@@ -0,0 +1,45 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Contracts::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Contracts::Address)
+                            .custom(Alias::new("citext"))
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Contracts::ProjectId).uuid().unique_key())
+                    .col(
+                        ColumnDef::new(Contracts::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .extra("default now()".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Contracts::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum Contracts {
+    Table,
+    Address,
+    ProjectId,
+    CreatedAt,
+}
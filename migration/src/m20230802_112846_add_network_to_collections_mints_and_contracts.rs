@@ -0,0 +1,152 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, Statement},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Collections::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Collections::Network)
+                            .text()
+                            .not_null()
+                            .default("polygon"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Mints::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Mints::Network)
+                            .text()
+                            .not_null()
+                            .default("polygon"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Contracts::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Contracts::Network)
+                            .text()
+                            .not_null()
+                            .default("polygon"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+
+        let stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"alter table contracts
+            drop constraint contracts_pkey,
+            drop constraint contracts_project_id_key,
+            add primary key (network, address);"#
+                .to_string(),
+        );
+
+        db.execute(stmt).await?;
+
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("contracts-network-project_id-idx")
+                    .table(Contracts::Table)
+                    .col(Contracts::Network)
+                    .col(Contracts::ProjectId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("contracts-network-project_id-idx")
+                    .table(Contracts::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+
+        let stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"alter table contracts
+            drop constraint contracts_pkey,
+            add primary key (address),
+            add constraint contracts_project_id_key unique (project_id);"#
+                .to_string(),
+        );
+
+        db.execute(stmt).await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Contracts::Table)
+                    .drop_column(Contracts::Network)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Mints::Table)
+                    .drop_column(Mints::Network)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Collections::Table)
+                    .drop_column(Collections::Network)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Collections {
+    Table,
+    Network,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Mints {
+    Table,
+    Network,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Contracts {
+    Table,
+    Network,
+    ProjectId,
+}
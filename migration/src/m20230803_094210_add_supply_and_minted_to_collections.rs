@@ -0,0 +1,60 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, Statement},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Collections::Table)
+                    .add_column_if_not_exists(ColumnDef::new(Collections::Supply).big_integer())
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Collections::Minted)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+
+        let stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"update collections set minted = coalesce(
+            (select sum(amount) from mints where mints.collection_id = collections.id), 0);"#
+                .to_string(),
+        );
+
+        db.execute(stmt).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Collections::Table)
+                    .drop_column(Collections::Supply)
+                    .drop_column(Collections::Minted)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Collections {
+    Table,
+    Supply,
+    Minted,
+}
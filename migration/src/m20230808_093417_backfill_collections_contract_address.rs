@@ -0,0 +1,89 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, Statement},
+};
+
+/// The edition contract drops created before contract addresses were recorded live on
+const LEGACY_EDITION_CONTRACT: &str = "LEGACY_EDITION_CONTRACT";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"select count(*) as missing from collections where contract_address is null;"#
+                .to_string(),
+        );
+
+        let missing = match db.query_one(stmt).await? {
+            Some(row) => row.try_get::<i64>("", "missing")?,
+            None => 0,
+        };
+
+        if missing > 0 {
+            let contract = std::env::var(LEGACY_EDITION_CONTRACT)
+                .ok()
+                .filter(|c| {
+                    c.len() == 42
+                        && c.starts_with("0x")
+                        && c[2..].chars().all(|c| c.is_ascii_hexdigit())
+                })
+                .ok_or_else(|| {
+                    DbErr::Migration(format!(
+                        "{missing} collections have no contract address. Set \
+                         {LEGACY_EDITION_CONTRACT} to the edition contract they were created \
+                         on, the value of POLYGON_EDITION_CONTRACT before it was first changed, \
+                         and run this migration again"
+                    ))
+                })?;
+
+            let stmt = Statement::from_sql_and_values(
+                manager.get_database_backend(),
+                r#"update collections set contract_address = $1 where contract_address is null;"#,
+                [contract.into()],
+            );
+
+            db.execute(stmt).await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Collections::Table)
+                    .modify_column(
+                        ColumnDef::new(Collections::ContractAddress)
+                            .custom(Alias::new("citext"))
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Collections::Table)
+                    .modify_column(
+                        ColumnDef::new(Collections::ContractAddress)
+                            .custom(Alias::new("citext"))
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Collections {
+    Table,
+    ContractAddress,
+}
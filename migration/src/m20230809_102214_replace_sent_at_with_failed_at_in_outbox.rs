@@ -0,0 +1,93 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, Statement},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // Sent rows are deleted from now on, so the ones kept so far are pruned as well
+        let stmt = Statement::from_string(
+            manager.get_database_backend(),
+            "delete from outbox where sent_at is not null;".to_string(),
+        );
+
+        db.execute(stmt).await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("outbox_sent_at_created_at_idx")
+                    .table(Outbox::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Outbox::Table)
+                    .drop_column(Outbox::SentAt)
+                    .add_column_if_not_exists(ColumnDef::new(Outbox::FailedAt).timestamp())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("outbox_failed_at_created_at_idx")
+                    .table(Outbox::Table)
+                    .col(Outbox::FailedAt)
+                    .col(Outbox::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("outbox_failed_at_created_at_idx")
+                    .table(Outbox::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Outbox::Table)
+                    .drop_column(Outbox::FailedAt)
+                    .add_column_if_not_exists(ColumnDef::new(Outbox::SentAt).timestamp())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("outbox_sent_at_created_at_idx")
+                    .table(Outbox::Table)
+                    .col(Outbox::SentAt)
+                    .col(Outbox::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Outbox {
+    Table,
+    CreatedAt,
+    SentAt,
+    FailedAt,
+}